@@ -0,0 +1,23 @@
+// Bakes the compiler version and target triple into the binary for the
+// results (language_version) and the run manifest.
+
+use std::env;
+use std::process::Command;
+
+fn main() {
+	let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+	let verbose = Command::new(rustc)
+		.arg("-vV")
+		.output()
+		.ok()
+		.and_then(|o| String::from_utf8(o.stdout).ok())
+		.unwrap_or_default();
+	let field = |key: &str| verbose.lines().find_map(|l| l.strip_prefix(key)).unwrap_or("unknown").trim().to_string();
+
+	println!("cargo:rustc-env=BENCH_RUSTC_RELEASE={}", field("release:"));
+	println!("cargo:rustc-env=BENCH_RUSTC_VERSION={}", verbose.lines().next().unwrap_or("unknown"));
+	println!("cargo:rustc-env=BENCH_TARGET={}", env::var("TARGET").unwrap_or_else(|_| "unknown".to_string()));
+	println!("cargo:rustc-env=BENCH_PROFILE={}", env::var("PROFILE").unwrap_or_else(|_| "unknown".to_string()));
+	println!("cargo:rerun-if-changed=build.rs");
+	println!("cargo:rerun-if-env-changed=RUSTC");
+}
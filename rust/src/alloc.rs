@@ -0,0 +1,111 @@
+// --track-alloc: a global allocator that forwards to System and, once
+// enabled, tallies what happens between start() and stop(). When the flag is
+// off the only cost is one relaxed load per allocation.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+
+pub struct CountingAlloc;
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static COUNT: AtomicU64 = AtomicU64::new(0);
+// Signed: blocks allocated before enable() may be freed afterwards.
+static LIVE: AtomicI64 = AtomicI64::new(0);
+static PEAK: AtomicI64 = AtomicI64::new(0);
+static BASE: AtomicI64 = AtomicI64::new(0);
+
+fn on_alloc(size: usize) {
+	BYTES.fetch_add(size as u64, Ordering::Relaxed);
+	COUNT.fetch_add(1, Ordering::Relaxed);
+	let live = LIVE.fetch_add(size as i64, Ordering::Relaxed) + size as i64;
+	PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+fn on_dealloc(size: usize) {
+	LIVE.fetch_sub(size as i64, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		let p = System.alloc(layout);
+		if !p.is_null() && ENABLED.load(Ordering::Relaxed) {
+			on_alloc(layout.size());
+		}
+		p
+	}
+
+	unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+		let p = System.alloc_zeroed(layout);
+		if !p.is_null() && ENABLED.load(Ordering::Relaxed) {
+			on_alloc(layout.size());
+		}
+		p
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		System.dealloc(ptr, layout);
+		if ENABLED.load(Ordering::Relaxed) {
+			on_dealloc(layout.size());
+		}
+	}
+
+	// A realloc counts as one allocation of the new size (a Vec growing is
+	// what it usually is) and moves the live total by the difference.
+	unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+		let p = System.realloc(ptr, layout, new_size);
+		if !p.is_null() && ENABLED.load(Ordering::Relaxed) {
+			on_dealloc(layout.size());
+			on_alloc(new_size);
+		}
+		p
+	}
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AllocStats {
+	pub bytes: u64,
+	pub count: u64,
+	// Highest live heap above what was live at start()
+	pub peak_bytes: u64,
+}
+
+pub fn enable() {
+	ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn start() {
+	BYTES.store(0, Ordering::Relaxed);
+	COUNT.store(0, Ordering::Relaxed);
+	let live = LIVE.load(Ordering::Relaxed);
+	BASE.store(live, Ordering::Relaxed);
+	PEAK.store(live, Ordering::Relaxed);
+}
+
+pub fn stop() -> AllocStats {
+	AllocStats {
+		bytes: BYTES.load(Ordering::Relaxed),
+		count: COUNT.load(Ordering::Relaxed),
+		peak_bytes: (PEAK.load(Ordering::Relaxed) - BASE.load(Ordering::Relaxed)).max(0) as u64,
+	}
+}
+
+// Process-lifetime high-water mark of resident memory, from getrusage.
+#[cfg(unix)]
+pub fn peak_rss_kb() -> Option<u64> {
+	let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+	if unsafe { libc::getrusage(libc::RUSAGE_SELF, &mut usage) } != 0 {
+		return None;
+	}
+	// Linux reports kilobytes, macOS bytes.
+	let rss = usage.ru_maxrss as u64;
+	Some(if cfg!(target_os = "macos") { rss / 1024 } else { rss })
+}
+
+#[cfg(not(unix))]
+pub fn peak_rss_kb() -> Option<u64> {
+	None
+}
@@ -0,0 +1,164 @@
+// McIlroy's "A Killer Adversary for Quicksort" (1999): sort n placeholder
+// elements whose values are decided lazily, inside the comparator. Every
+// element starts as "gas" (larger than anything decided so far); when two gas
+// elements meet, the one that is not the current pivot candidate is frozen
+// to the next-smallest value. The values fixed by the end form an input on
+// which that same sort makes as many comparisons as the adversary can force.
+//
+// Sorts that pre-scan for natural runs defeat it: std's sort_unstable and
+// sort both start by comparing neighbours, which freezes the gas elements in
+// ascending order, so antiqsort_builtin_* is just an already-sorted input
+// (n - 1 comparisons). Shell sort ends up the same way. generate() says so
+// whenever the result is sorted.
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+
+use crate::elem::{Elem, IntElem};
+use crate::sorters::{self, CountOps, SortConfig, Sorter};
+use crate::tasks::DEFAULT_MAX_QUADRATIC_N;
+
+struct Adversary {
+	val: Vec<u32>,
+	gas: u32,
+	nsolid: u32,
+	candidate: usize,
+	comparisons: u64,
+}
+
+impl Adversary {
+	fn new(n: usize) -> Self {
+		let gas = n.saturating_sub(1) as u32;
+		Adversary { val: vec![gas; n], gas, nsolid: 0, candidate: 0, comparisons: 0 }
+	}
+
+	fn freeze(&mut self, x: usize) {
+		self.val[x] = self.nsolid;
+		self.nsolid += 1;
+	}
+
+	fn cmp(&mut self, x: usize, y: usize) -> Ordering {
+		self.comparisons += 1;
+		if self.val[x] == self.gas && self.val[y] == self.gas {
+			if x == self.candidate {
+				self.freeze(x);
+			} else {
+				self.freeze(y);
+			}
+		}
+		if self.val[x] == self.gas {
+			self.candidate = x;
+		} else if self.val[y] == self.gas {
+			self.candidate = y;
+		}
+		self.val[x].cmp(&self.val[y])
+	}
+}
+
+thread_local! {
+	static ADVERSARY: RefCell<Adversary> = RefCell::new(Adversary::new(0));
+}
+
+// An index into the adversary's values; comparing two of them asks the
+// adversary. Only ever sorted in memory, on the thread that set it up.
+#[derive(Clone, Copy, Debug, Default)]
+struct Adv(u32);
+
+impl Ord for Adv {
+	fn cmp(&self, other: &Self) -> Ordering {
+		ADVERSARY.with_borrow_mut(|a| a.cmp(self.0 as usize, other.0 as usize))
+	}
+}
+
+impl PartialOrd for Adv {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl PartialEq for Adv {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for Adv {}
+
+impl Elem for Adv {
+	const NAME: &'static str = "adv";
+	const BYTES: usize = 4;
+
+	fn from_le(_bytes: &[u8]) -> Self {
+		unreachable!("antiqsort elements are never loaded from a file")
+	}
+}
+
+impl CountOps for Adv {}
+
+impl IntElem for Adv {
+	fn radix_key(self) -> u64 {
+		unreachable!("antiqsort only drives comparison sorts")
+	}
+}
+
+fn find_sorter(algo: &str) -> Result<Box<dyn Sorter<Adv>>, String> {
+	let sorter = sorters::find::<Adv>(algo, &SortConfig::default())
+		.ok_or_else(|| format!("antiqsort: unknown algo {} (see --list-algos)", algo))?;
+	if !sorter.is_comparison() {
+		return Err(format!("antiqsort: {} is not a comparison sort", sorter.name()));
+	}
+	if sorter.is_parallel() {
+		return Err(format!("antiqsort: {} compares on several threads", sorter.name()));
+	}
+	Ok(sorter)
+}
+
+// Whether `algo` can be attacked, without running it.
+pub fn check_algo(algo: &str) -> Result<(), String> {
+	find_sorter(algo).map(|_| ())
+}
+
+fn check_n(sorter: &dyn Sorter<Adv>, n: usize) -> Result<(), String> {
+	// The adversary makes a quadratic sort take its full n^2/2 comparisons.
+	if sorter.is_quadratic() && n > DEFAULT_MAX_QUADRATIC_N {
+		return Err(format!(
+			"antiqsort: {} is quadratic; refusing n={} (limit {})",
+			sorter.name(),
+			n,
+			DEFAULT_MAX_QUADRATIC_N
+		));
+	}
+	if n > i32::MAX as usize {
+		return Err(format!("antiqsort: n={} does not fit i32 values", n));
+	}
+	Ok(())
+}
+
+// Whether an input of length n can be generated for `algo`, without doing it.
+pub fn check_size(algo: &str, n: usize) -> Result<(), String> {
+	check_n(find_sorter(algo)?.as_ref(), n)
+}
+
+// The adversarial input of length n for --algo `algo`.
+pub fn generate(algo: &str, n: usize) -> Result<Vec<i32>, String> {
+	let mut sorter = find_sorter(algo)?;
+	check_n(sorter.as_ref(), n)?;
+
+	ADVERSARY.set(Adversary::new(n));
+	let mut ptr: Vec<Adv> = (0..n as u32).map(Adv).collect();
+	sorter.sort(&mut ptr);
+	let adv = ADVERSARY.replace(Adversary::new(0));
+
+	let nlogn = n as f64 * (n.max(2) as f64).log2();
+	eprintln!(
+		"[antiqsort] {} n={} comparisons={} ({:.2} x n log2 n)",
+		sorter.name(),
+		n,
+		adv.comparisons,
+		adv.comparisons as f64 / nlogn
+	);
+	if n > 1 && adv.val.windows(2).all(|w| w[0] <= w[1]) {
+		eprintln!("[antiqsort] note: {} froze every element in ascending order; this input is just sorted", sorter.name());
+	}
+	Ok(adv.val.into_iter().map(|x| x as i32).collect())
+}
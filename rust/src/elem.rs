@@ -0,0 +1,210 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+
+use crate::sorters::CountOps;
+
+// Fixed-size element types a dataset can hold: u32 n followed by n
+// little-endian values. Integer types (IntElem) work with every sorter;
+// floats (FloatElem) have their own sort modes; every other task is i32-only.
+// String datasets have their own length-prefixed format (ElemType::Str).
+pub trait Elem: Copy + Default + Debug + Send + Sync + 'static {
+	// Written to the elem_type column and used in dataset file names.
+	const NAME: &'static str;
+	const BYTES: usize;
+
+	// `bytes` is exactly BYTES long.
+	fn from_le(bytes: &[u8]) -> Self;
+}
+
+pub trait IntElem: Elem + Ord + CountOps {
+	// Order-preserving map onto unsigned integers, for radix sorts: only the
+	// low BYTES bytes are significant.
+	fn radix_key(self) -> u64;
+}
+
+pub trait FloatElem: Elem + PartialOrd + CountOps {
+	fn total_cmp(&self, other: &Self) -> Ordering;
+	fn is_nan(self) -> bool;
+
+	// Unsigned key of the bit pattern whose order matches total_cmp:
+	// -NaN < -inf < ... < -0.0 < +0.0 < ... < +inf < +NaN.
+	fn radix_key(self) -> u64;
+}
+
+impl Elem for i32 {
+	const NAME: &'static str = "i32";
+	const BYTES: usize = 4;
+
+	fn from_le(bytes: &[u8]) -> Self {
+		i32::from_le_bytes(bytes.try_into().unwrap())
+	}
+}
+
+impl IntElem for i32 {
+	// Flipping the sign bit maps i32 onto u32 so that unsigned order matches
+	// signed order (i32::MIN -> 0, -1 -> 0x7fff_ffff, 0 -> 0x8000_0000).
+	#[inline]
+	fn radix_key(self) -> u64 {
+		((self as u32) ^ 0x8000_0000) as u64
+	}
+}
+
+impl Elem for i64 {
+	const NAME: &'static str = "i64";
+	const BYTES: usize = 8;
+
+	fn from_le(bytes: &[u8]) -> Self {
+		i64::from_le_bytes(bytes.try_into().unwrap())
+	}
+}
+
+impl IntElem for i64 {
+	#[inline]
+	fn radix_key(self) -> u64 {
+		(self as u64) ^ (1 << 63)
+	}
+}
+
+impl Elem for u64 {
+	const NAME: &'static str = "u64";
+	const BYTES: usize = 8;
+
+	fn from_le(bytes: &[u8]) -> Self {
+		u64::from_le_bytes(bytes.try_into().unwrap())
+	}
+}
+
+impl IntElem for u64 {
+	#[inline]
+	fn radix_key(self) -> u64 {
+		self
+	}
+}
+
+impl Elem for f32 {
+	const NAME: &'static str = "f32";
+	const BYTES: usize = 4;
+
+	fn from_le(bytes: &[u8]) -> Self {
+		f32::from_le_bytes(bytes.try_into().unwrap())
+	}
+}
+
+impl FloatElem for f32 {
+	fn total_cmp(&self, other: &Self) -> Ordering {
+		f32::total_cmp(self, other)
+	}
+
+	fn is_nan(self) -> bool {
+		f32::is_nan(self)
+	}
+
+	// Negative values: flip every bit (larger magnitude sorts lower);
+	// positive values: set the sign bit so they sort above all negatives.
+	#[inline]
+	fn radix_key(self) -> u64 {
+		let bits = self.to_bits();
+		(if bits >> 31 == 1 { !bits } else { bits | 0x8000_0000 }) as u64
+	}
+}
+
+impl Elem for f64 {
+	const NAME: &'static str = "f64";
+	const BYTES: usize = 8;
+
+	fn from_le(bytes: &[u8]) -> Self {
+		f64::from_le_bytes(bytes.try_into().unwrap())
+	}
+}
+
+impl FloatElem for f64 {
+	fn total_cmp(&self, other: &Self) -> Ordering {
+		f64::total_cmp(self, other)
+	}
+
+	fn is_nan(self) -> bool {
+		f64::is_nan(self)
+	}
+
+	#[inline]
+	fn radix_key(self) -> u64 {
+		let bits = self.to_bits();
+		if bits >> 63 == 1 {
+			!bits
+		} else {
+			bits | (1 << 63)
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ElemType {
+	I32,
+	I64,
+	U64,
+	F32,
+	F64,
+	Str,
+}
+
+impl ElemType {
+	pub fn parse(s: &str) -> Option<ElemType> {
+		match s {
+			"i32" => Some(ElemType::I32),
+			"i64" => Some(ElemType::I64),
+			"u64" => Some(ElemType::U64),
+			"f32" => Some(ElemType::F32),
+			"f64" => Some(ElemType::F64),
+			"str" => Some(ElemType::Str),
+			_ => None,
+		}
+	}
+
+	// Written to the elem_type column.
+	pub fn name(self) -> &'static str {
+		match self {
+			ElemType::I32 => i32::NAME,
+			ElemType::I64 => i64::NAME,
+			ElemType::U64 => u64::NAME,
+			ElemType::F32 => f32::NAME,
+			ElemType::F64 => f64::NAME,
+			ElemType::Str => "str",
+		}
+	}
+
+	// Non-i32 datasets are named "<dist>_n<n>_seed<seed>.<type>.bin";
+	// a plain ".bin" is the original i32 format.
+	pub fn from_path(path: &str) -> ElemType {
+		for (suffix, t) in [
+			(".i64.bin", ElemType::I64),
+			(".u64.bin", ElemType::U64),
+			(".f32.bin", ElemType::F32),
+			(".f64.bin", ElemType::F64),
+			(".str.bin", ElemType::Str),
+		] {
+			if path.ends_with(suffix) {
+				return t;
+			}
+		}
+		ElemType::I32
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// -NaN < -inf < ... < -0.0 < +0.0 < ... < +inf < +NaN, as total_cmp orders them.
+	fn check_order<T: FloatElem>(ordered: &[T]) {
+		for w in ordered.windows(2) {
+			assert!(w[0].radix_key() < w[1].radix_key(), "{:?} < {:?} ({})", w[0], w[1], T::NAME);
+			assert_eq!(w[0].total_cmp(&w[1]), Ordering::Less);
+		}
+	}
+
+	#[test]
+	fn float_radix_key_order() {
+		check_order::<f32>(&[-f32::NAN, f32::NEG_INFINITY, -1.5, -f32::MIN_POSITIVE, -0.0, 0.0, f32::MIN_POSITIVE, 1.5, f32::INFINITY, f32::NAN]);
+		check_order::<f64>(&[-f64::NAN, f64::NEG_INFINITY, -1.5, -f64::MIN_POSITIVE, -0.0, 0.0, f64::MIN_POSITIVE, 1.5, f64::INFINITY, f64::NAN]);
+	}
+}
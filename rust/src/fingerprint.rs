@@ -0,0 +1,113 @@
+// What machine and toolchain produced a run, for the run manifest. Build-time
+// facts come from build.rs; the rest is read from /proc and /sys and is left
+// out (None) where the platform or a VM does not expose it.
+
+use std::collections::BTreeSet;
+use std::fs;
+
+pub const RUSTC_VERSION: &str = env!("BENCH_RUSTC_VERSION");
+pub const RUSTC_RELEASE: &str = env!("BENCH_RUSTC_RELEASE");
+pub const TARGET: &str = env!("BENCH_TARGET");
+pub const PROFILE: &str = env!("BENCH_PROFILE");
+
+pub struct Fingerprint {
+	pub hostname: Option<String>,
+	pub kernel: Option<String>,
+	pub cpu_model: Option<String>,
+	pub cpu_flags: Vec<String>,
+	pub logical_cpus: Option<usize>,
+	pub physical_cores: Option<usize>,
+	// Honours the affinity mask, so --pin-cpu shows up here as 1
+	pub available_parallelism: Option<usize>,
+	pub governor: Option<String>,
+	pub turbo: Option<bool>,
+}
+
+fn read_trimmed(path: &str) -> Option<String> {
+	fs::read_to_string(path).ok().map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+fn cpuinfo_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+	let (k, v) = line.split_once(':')?;
+	(k.trim() == key).then(|| v.trim())
+}
+
+// Distinct scaling governors across CPUs, comma-joined ("performance", or
+// "performance,powersave" on a mixed box); None without cpufreq.
+pub fn governor() -> Option<String> {
+	let mut seen = BTreeSet::new();
+	for entry in fs::read_dir("/sys/devices/system/cpu").ok()?.flatten() {
+		let name = entry.file_name();
+		let name = name.to_string_lossy();
+		if !name.strip_prefix("cpu").is_some_and(|i| !i.is_empty() && i.bytes().all(|b| b.is_ascii_digit())) {
+			continue;
+		}
+		if let Some(g) = read_trimmed(&format!("/sys/devices/system/cpu/{}/cpufreq/scaling_governor", name)) {
+			seen.insert(g);
+		}
+	}
+	(!seen.is_empty()).then(|| seen.into_iter().collect::<Vec<_>>().join(","))
+}
+
+// Whether turbo/boost is enabled: intel_pstate's no_turbo, else the generic
+// cpufreq boost switch (acpi-cpufreq, amd-pstate).
+pub fn turbo() -> Option<bool> {
+	if let Some(v) = read_trimmed("/sys/devices/system/cpu/intel_pstate/no_turbo") {
+		return Some(v == "0");
+	}
+	read_trimmed("/sys/devices/system/cpu/cpufreq/boost").map(|v| v == "1")
+}
+
+fn hostname() -> Option<String> {
+	if let Some(h) = read_trimmed("/proc/sys/kernel/hostname") {
+		return Some(h);
+	}
+	let mut buf = [0u8; 256];
+	if unsafe { libc::gethostname(buf.as_mut_ptr() as *mut libc::c_char, buf.len()) } != 0 {
+		return None;
+	}
+	let end = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+	Some(String::from_utf8_lossy(&buf[..end]).into_owned())
+}
+
+pub fn collect() -> Fingerprint {
+	let cpuinfo = fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+	let mut cpu_model = None;
+	let mut cpu_flags = Vec::new();
+	let mut logical = 0;
+	let mut cores = BTreeSet::new();
+	let mut physical_id = None;
+	for line in cpuinfo.lines() {
+		if cpuinfo_value(line, "processor").is_some() {
+			logical += 1;
+		}
+		// x86 says "model name"; ARM kernels say "Hardware" or nothing at all.
+		if cpu_model.is_none() {
+			cpu_model = cpuinfo_value(line, "model name").or_else(|| cpuinfo_value(line, "Hardware")).map(str::to_string);
+		}
+		// x86 "flags", ARM "Features"
+		if cpu_flags.is_empty() {
+			if let Some(f) = cpuinfo_value(line, "flags").or_else(|| cpuinfo_value(line, "Features")) {
+				cpu_flags = f.split_whitespace().map(str::to_string).collect();
+			}
+		}
+		if let Some(p) = cpuinfo_value(line, "physical id") {
+			physical_id = Some(p.to_string());
+		}
+		if let Some(c) = cpuinfo_value(line, "core id") {
+			cores.insert((physical_id.clone(), c.to_string()));
+		}
+	}
+
+	Fingerprint {
+		hostname: hostname(),
+		kernel: read_trimmed("/proc/sys/kernel/osrelease"),
+		cpu_model,
+		cpu_flags,
+		logical_cpus: (logical > 0).then_some(logical),
+		physical_cores: (!cores.is_empty()).then_some(cores.len()),
+		available_parallelism: std::thread::available_parallelism().ok().map(|n| n.get()),
+		governor: governor(),
+		turbo: turbo(),
+	}
+}
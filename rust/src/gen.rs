@@ -0,0 +1,697 @@
+// `bench gen`: the Rust port of scripts/gen_datasets.py, for environments
+// without Python. Same element types, distributions, file format, file names
+// and manifest layout; the values differ, since Python's Mersenne Twister is
+// not reproduced. The manifest's generator column records which generator
+// wrote each file, and --force refuses to overwrite files gen_datasets.py
+// wrote, so a checkout's datasets never silently change contents.
+// generate() is also the in-process generator behind `--dataset gen:<name>`.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::antiqsort;
+use crate::elem::{Elem, ElemType};
+
+// Written to the manifest's generator column.
+const GENERATOR: &str = "bench-gen";
+const PYTHON_GENERATOR: &str = "gen_datasets.py";
+
+const DEFAULT_DISTS: &str = "random,sorted,reversed,dups,nearly_sorted";
+const DEFAULT_STR_DISTS: &str = "random_ascii,shared_prefix,urls";
+const EXTRA_DISTS: &str =
+	"organ_pipe,sawtooth,all_equal,few_unique[_k<k>],sorted_runs[_k<k>],sorted_random_tail,zipf,gaussian,antiqsort_<algo>";
+
+// k when few_unique / sorted_runs are named without one.
+const DEFAULT_FEW_UNIQUE_K: usize = 16;
+const DEFAULT_SORTED_RUNS_K: usize = 1000;
+const DEFAULT_SIZES: &[usize] = &[1_000, 10_000, 100_000, 1_000_000];
+
+fn usage_and_exit() -> ! {
+	eprintln!(
+		"Usage:
+  cargo run -- gen [--outdir datasets/ints] [--meta-dir datasets/meta] [--sizes 1000,10000,...]
+                   [--seeds 1,2,...] [--dists {}] [--force] [--dry-run]
+                   [--elem-type i32|i64|u64|f32|f64|str]
+  also, i32 only: --dists {}
+  f32/f64 also: --dists specials
+  str: --dists {}",
+		DEFAULT_DISTS, EXTRA_DISTS, DEFAULT_STR_DISTS
+	);
+	std::process::exit(2);
+}
+
+// SplitMix64: tiny, seedable and good enough for benchmark inputs.
+pub struct Rng(u64);
+
+impl Rng {
+	pub fn new(seed: u64) -> Self {
+		Rng(seed)
+	}
+
+	pub fn next_u64(&mut self) -> u64 {
+		self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+		let mut z = self.0;
+		z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+		z ^ (z >> 31)
+	}
+
+	// Uniform in 0..n (multiply-shift; the bias is below 2^-32 for our n).
+	pub fn below(&mut self, n: usize) -> usize {
+		((self.next_u64() as u128 * n as u128) >> 64) as usize
+	}
+
+	pub fn next_i32(&mut self) -> i32 {
+		(self.next_u64() >> 32) as u32 as i32
+	}
+
+	// Uniform in [0, 1).
+	pub fn next_f64(&mut self) -> f64 {
+		(self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+	}
+}
+
+// Numeric element types; the value ranges follow gen_datasets.py: the full
+// range for integers, uniform in [-1e9, 1e9] for floats.
+trait GenNum: Elem + PartialOrd {
+	fn draw(rng: &mut Rng) -> Self;
+
+	// x in 0..128, for dups
+	fn small(x: usize) -> Self;
+
+	// NaN, infinities and signed zeros; None for integer types.
+	fn special(_x: f64) -> Option<Self> {
+		None
+	}
+
+	fn write_le(self, out: &mut impl Write) -> io::Result<()>;
+}
+
+impl GenNum for i32 {
+	fn draw(rng: &mut Rng) -> Self {
+		rng.next_i32()
+	}
+
+	fn small(x: usize) -> Self {
+		x as i32
+	}
+
+	fn write_le(self, out: &mut impl Write) -> io::Result<()> {
+		out.write_all(&self.to_le_bytes())
+	}
+}
+
+impl GenNum for i64 {
+	fn draw(rng: &mut Rng) -> Self {
+		rng.next_u64() as i64
+	}
+
+	fn small(x: usize) -> Self {
+		x as i64
+	}
+
+	fn write_le(self, out: &mut impl Write) -> io::Result<()> {
+		out.write_all(&self.to_le_bytes())
+	}
+}
+
+impl GenNum for u64 {
+	fn draw(rng: &mut Rng) -> Self {
+		rng.next_u64()
+	}
+
+	fn small(x: usize) -> Self {
+		x as u64
+	}
+
+	fn write_le(self, out: &mut impl Write) -> io::Result<()> {
+		out.write_all(&self.to_le_bytes())
+	}
+}
+
+impl GenNum for f32 {
+	fn draw(rng: &mut Rng) -> Self {
+		(rng.next_f64() * 2e9 - 1e9) as f32
+	}
+
+	fn small(x: usize) -> Self {
+		x as f32
+	}
+
+	fn special(x: f64) -> Option<Self> {
+		Some(x as f32)
+	}
+
+	fn write_le(self, out: &mut impl Write) -> io::Result<()> {
+		out.write_all(&self.to_le_bytes())
+	}
+}
+
+impl GenNum for f64 {
+	fn draw(rng: &mut Rng) -> Self {
+		rng.next_f64() * 2e9 - 1e9
+	}
+
+	fn small(x: usize) -> Self {
+		x as f64
+	}
+
+	fn special(x: f64) -> Option<Self> {
+		Some(x)
+	}
+
+	fn write_le(self, out: &mut impl Write) -> io::Result<()> {
+		out.write_all(&self.to_le_bytes())
+	}
+}
+
+const FLOAT_SPECIALS: [f64; 6] = [f64::NAN, -f64::NAN, f64::INFINITY, f64::NEG_INFINITY, -0.0, 0.0];
+
+fn gen_random<T: GenNum>(rng: &mut Rng, n: usize) -> Vec<T> {
+	(0..n).map(|_| T::draw(rng)).collect()
+}
+
+// No NaNs yet at this point, so partial_cmp is total.
+fn gen_sorted<T: GenNum>(rng: &mut Rng, n: usize) -> Vec<T> {
+	let mut v: Vec<T> = gen_random(rng, n);
+	v.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+	v
+}
+
+fn gen_reversed<T: GenNum>(rng: &mut Rng, n: usize) -> Vec<T> {
+	let mut v: Vec<T> = gen_sorted(rng, n);
+	v.reverse();
+	v
+}
+
+// Many duplicates: values from 0..distinct.
+fn gen_dups<T: GenNum>(rng: &mut Rng, n: usize, distinct: usize) -> Vec<T> {
+	(0..n).map(|_| T::small(rng.below(distinct))).collect()
+}
+
+// Sorted, then n * swap_fraction random swaps.
+fn gen_nearly_sorted<T: GenNum>(rng: &mut Rng, n: usize, swap_fraction: f64) -> Vec<T> {
+	let mut v: Vec<T> = gen_sorted(rng, n);
+	if n > 1 {
+		for _ in 0..(n as f64 * swap_fraction) as usize {
+			let (i, j) = (rng.below(n), rng.below(n));
+			v.swap(i, j);
+		}
+	}
+	v
+}
+
+// Random floats with special_fraction * n NaN/inf/signed-zero values written
+// over random positions.
+fn gen_specials<T: GenNum>(rng: &mut Rng, n: usize, special_fraction: f64) -> Vec<T> {
+	let mut v: Vec<T> = gen_random(rng, n);
+	for _ in 0..(n as f64 * special_fraction) as usize {
+		let i = rng.below(n);
+		v[i] = T::special(FLOAT_SPECIALS[rng.below(FLOAT_SPECIALS.len())]).expect("specials is float-only");
+	}
+	v
+}
+
+const ALNUM: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+const URL_HOSTS: &[&str] = &["www.example.com", "api.example.com", "cdn.example.net", "shop.example.org", "docs.example.io"];
+const URL_WORDS: &[&str] = &["users", "items", "search", "static", "v1", "v2", "images", "orders", "account", "blog", "tags"];
+
+// lo..=hi random alphanumeric characters.
+fn random_word(rng: &mut Rng, lo: usize, hi: usize) -> String {
+	let len = lo + rng.below(hi - lo + 1);
+	(0..len).map(|_| ALNUM[rng.below(ALNUM.len())] as char).collect()
+}
+
+fn gen_random_ascii(rng: &mut Rng, n: usize) -> Vec<String> {
+	(0..n).map(|_| random_word(rng, 4, 32)).collect()
+}
+
+// Comparisons have to walk the whole prefix before they find a difference.
+fn gen_shared_prefix(rng: &mut Rng, n: usize, prefixes: usize, prefix_len: usize) -> Vec<String> {
+	let pool: Vec<String> = (0..prefixes).map(|_| random_word(rng, prefix_len, prefix_len)).collect();
+	(0..n).map(|_| format!("{}{}", pool[rng.below(prefixes)], random_word(rng, 1, 8))).collect()
+}
+
+// scheme://host/path/word[?id=N], hosts and path words from small pools.
+fn gen_urls(rng: &mut Rng, n: usize) -> Vec<String> {
+	(0..n)
+		.map(|_| {
+			let scheme = if rng.below(2) == 0 { "https" } else { "http" };
+			let host = URL_HOSTS[rng.below(URL_HOSTS.len())];
+			let path: Vec<&str> = (0..1 + rng.below(4)).map(|_| URL_WORDS[rng.below(URL_WORDS.len())]).collect();
+			let mut url = format!("{}://{}/{}/{}", scheme, host, path.join("/"), random_word(rng, 4, 12));
+			if rng.below(2) == 0 {
+				url.push_str(&format!("?id={}", rng.below(1_000_000)));
+			}
+			url
+		})
+		.collect()
+}
+
+// Ascending to the middle, then descending: 0 1 2 .. 2 1 0.
+fn gen_organ_pipe(n: usize) -> Vec<i32> {
+	(0..n).map(|i| i.min(n - 1 - i) as i32).collect()
+}
+
+// Repeated ascending ramps of length ~sqrt(n).
+fn gen_sawtooth(n: usize) -> Vec<i32> {
+	let period = ((n as f64).sqrt() as usize).max(1);
+	(0..n).map(|i| (i % period) as i32).collect()
+}
+
+fn gen_all_equal(rng: &mut Rng, n: usize) -> Vec<i32> {
+	vec![rng.next_i32(); n]
+}
+
+// k distinct random values, each element drawn uniformly among them.
+fn gen_few_unique(rng: &mut Rng, n: usize, k: usize) -> Vec<i32> {
+	let pool: Vec<i32> = (0..k).map(|_| rng.next_i32()).collect();
+	(0..n).map(|_| pool[rng.below(k)]).collect()
+}
+
+// Random values, sorted in consecutive chunks of length k.
+fn gen_sorted_runs(rng: &mut Rng, n: usize, k: usize) -> Vec<i32> {
+	let mut v: Vec<i32> = gen_random(rng, n);
+	for run in v.chunks_mut(k) {
+		run.sort_unstable();
+	}
+	v
+}
+
+// Sorted, then the last tail_fraction of the elements replaced by random values.
+fn gen_sorted_random_tail(rng: &mut Rng, n: usize, tail_fraction: f64) -> Vec<i32> {
+	let mut v: Vec<i32> = gen_sorted(rng, n);
+	let tail = (n as f64 * tail_fraction) as usize;
+	for x in &mut v[n - tail..] {
+		*x = rng.next_i32();
+	}
+	v
+}
+
+// Ranks 1..=n with P(r) proportional to 1 / r^s, by inverse CDF.
+fn gen_zipf(rng: &mut Rng, n: usize, s: f64) -> Vec<i32> {
+	let mut cdf = Vec::with_capacity(n);
+	let mut sum = 0.0;
+	for r in 1..=n {
+		sum += 1.0 / (r as f64).powf(s);
+		cdf.push(sum);
+	}
+	(0..n)
+		.map(|_| {
+			let u = rng.next_f64() * sum;
+			(cdf.partition_point(|&c| c < u).min(n - 1) + 1) as i32
+		})
+		.collect()
+}
+
+// Normal(0, sigma) rounded to i32, by Box-Muller.
+fn gen_gaussian(rng: &mut Rng, n: usize, sigma: f64) -> Vec<i32> {
+	(0..n)
+		.map(|_| {
+			let u1 = 1.0 - rng.next_f64();
+			let u2 = rng.next_f64();
+			let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+			(z * sigma).round() as i32
+		})
+		.collect()
+}
+
+// "few_unique_k16" -> ("few_unique", Some(16)); names without a k are returned as is.
+fn split_k(dist: &str) -> (&str, Option<usize>) {
+	if let Some((base, k)) = dist.rsplit_once("_k") {
+		if let Ok(k) = k.parse() {
+			return (base, Some(k));
+		}
+	}
+	(dist, None)
+}
+
+// The name written to file names and the distribution column: parameterised
+// distributions always carry their k.
+pub fn canonical_dist(dist: &str) -> Result<String, String> {
+	if let Some(algo) = dist.strip_prefix("antiqsort_") {
+		antiqsort::check_algo(algo)?;
+		return Ok(dist.to_string());
+	}
+	let (base, k) = split_k(dist);
+	let default_k = match base {
+		"few_unique" => DEFAULT_FEW_UNIQUE_K,
+		"sorted_runs" => DEFAULT_SORTED_RUNS_K,
+		_ => {
+			return match k {
+				None if generate(dist, 0, 0).is_ok() => Ok(dist.to_string()),
+				_ => Err(format!("Unknown distribution: {}", dist)),
+			}
+		}
+	};
+	match k.unwrap_or(default_k) {
+		0 => Err(format!("{}: k must be > 0", dist)),
+		k => Ok(format!("{}_k{}", base, k)),
+	}
+}
+
+pub fn generate(dist: &str, n: usize, seed: u64) -> Result<Vec<i32>, String> {
+	// Deterministic: the seed only appears in the file name.
+	if let Some(algo) = dist.strip_prefix("antiqsort_") {
+		return antiqsort::generate(algo, n);
+	}
+	let mut rng = Rng::new(seed);
+	let (base, k) = split_k(dist);
+	match (base, k) {
+		("random", None) => Ok(gen_random(&mut rng, n)),
+		("sorted", None) => Ok(gen_sorted(&mut rng, n)),
+		("reversed", None) => Ok(gen_reversed(&mut rng, n)),
+		("dups", None) => Ok(gen_dups(&mut rng, n, 128)),
+		("nearly_sorted", None) => Ok(gen_nearly_sorted(&mut rng, n, 0.01)),
+		("organ_pipe", None) => Ok(gen_organ_pipe(n)),
+		("sawtooth", None) => Ok(gen_sawtooth(n)),
+		("all_equal", None) => Ok(gen_all_equal(&mut rng, n)),
+		("few_unique", k) => Ok(gen_few_unique(&mut rng, n, k.unwrap_or(DEFAULT_FEW_UNIQUE_K).max(1))),
+		("sorted_runs", k) => Ok(gen_sorted_runs(&mut rng, n, k.unwrap_or(DEFAULT_SORTED_RUNS_K).max(1))),
+		("sorted_random_tail", None) => Ok(gen_sorted_random_tail(&mut rng, n, 0.1)),
+		("zipf", None) => Ok(gen_zipf(&mut rng, n, 1.1)),
+		("gaussian", None) => Ok(gen_gaussian(&mut rng, n, 1e6)),
+		_ => Err(format!("Unknown distribution: {}", dist)),
+	}
+}
+
+// Splits "<dist>_n<n>_seed<seed>" (a dataset file name without extension)
+// into its parts; dist may itself contain underscores.
+pub fn parse_name(stem: &str) -> Option<(&str, usize, u64)> {
+	let (rest, seed) = stem.rsplit_once("_seed")?;
+	let (dist, n) = rest.rsplit_once("_n")?;
+	Some((dist, n.parse().ok()?, seed.parse().ok()?))
+}
+
+// `--dataset gen:<dist>_n<n>_seed<seed>`: the data that file would hold under
+// `bench gen`, generated in memory instead.
+pub fn generate_named(name: &str) -> Result<Vec<i32>, String> {
+	let (dist, n, seed) = parse_name(name).ok_or_else(|| format!("gen:{}: expected gen:<dist>_n<n>_seed<seed>", name))?;
+	canonical_dist(dist)?;
+	generate(dist, n, seed)
+}
+
+// A generated dataset of any element type.
+pub enum Dataset {
+	I32(Vec<i32>),
+	I64(Vec<i64>),
+	U64(Vec<u64>),
+	F32(Vec<f32>),
+	F64(Vec<f64>),
+	Str(Vec<String>),
+}
+
+// The distributions every numeric type has (plus specials for floats); the
+// rest are i32-only, as in gen_datasets.py.
+fn generate_num<T: GenNum>(dist: &str, n: usize, seed: u64) -> Result<Vec<T>, String> {
+	let mut rng = Rng::new(seed);
+	match dist {
+		"random" => Ok(gen_random(&mut rng, n)),
+		"sorted" => Ok(gen_sorted(&mut rng, n)),
+		"reversed" => Ok(gen_reversed(&mut rng, n)),
+		"dups" => Ok(gen_dups(&mut rng, n, 128)),
+		"nearly_sorted" => Ok(gen_nearly_sorted(&mut rng, n, 0.01)),
+		"specials" if T::special(0.0).is_some() => Ok(gen_specials(&mut rng, n, 0.05)),
+		_ => Err(format!("Unknown distribution for --elem-type {}: {}", T::NAME, dist)),
+	}
+}
+
+fn generate_str(dist: &str, n: usize, seed: u64) -> Result<Vec<String>, String> {
+	let mut rng = Rng::new(seed);
+	match dist {
+		"random_ascii" => Ok(gen_random_ascii(&mut rng, n)),
+		"shared_prefix" => Ok(gen_shared_prefix(&mut rng, n, 4, 64)),
+		"urls" => Ok(gen_urls(&mut rng, n)),
+		_ => Err(format!("Unknown string distribution: {}", dist)),
+	}
+}
+
+pub fn generate_as(elem: ElemType, dist: &str, n: usize, seed: u64) -> Result<Dataset, String> {
+	match elem {
+		ElemType::I32 => generate(dist, n, seed).map(Dataset::I32),
+		ElemType::I64 => generate_num(dist, n, seed).map(Dataset::I64),
+		ElemType::U64 => generate_num(dist, n, seed).map(Dataset::U64),
+		ElemType::F32 => generate_num(dist, n, seed).map(Dataset::F32),
+		ElemType::F64 => generate_num(dist, n, seed).map(Dataset::F64),
+		ElemType::Str => generate_str(dist, n, seed).map(Dataset::Str),
+	}
+}
+
+// "<dist>_n<n>_seed<seed>.bin" for i32, ".<type>.bin" otherwise: the names
+// infer_distribution() and ElemType::from_path() parse.
+pub fn dataset_filename(dist: &str, elem: ElemType, n: usize, seed: u64) -> String {
+	match elem {
+		ElemType::I32 => format!("{}_n{}_seed{}.bin", dist, n, seed),
+		_ => format!("{}_n{}_seed{}.{}.bin", dist, n, seed, elem.name()),
+	}
+}
+
+fn len_u32(n: usize) -> io::Result<u32> {
+	u32::try_from(n).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "n does not fit in u32"))
+}
+
+// u32 n followed by n little-endian values; read back by read_bin_le.
+fn write_bin_le<T: GenNum>(path: &Path, values: &[T]) -> io::Result<()> {
+	let mut f = BufWriter::new(File::create(path)?);
+	f.write_all(&len_u32(values.len())?.to_le_bytes())?;
+	for &x in values {
+		x.write_le(&mut f)?;
+	}
+	f.flush()
+}
+
+// u32 n, then per string a u32 byte length and the UTF-8 bytes; read back by
+// read_bin_strings.
+fn write_bin_strings(path: &Path, values: &[String]) -> io::Result<()> {
+	let mut f = BufWriter::new(File::create(path)?);
+	f.write_all(&len_u32(values.len())?.to_le_bytes())?;
+	for s in values {
+		f.write_all(&len_u32(s.len())?.to_le_bytes())?;
+		f.write_all(s.as_bytes())?;
+	}
+	f.flush()
+}
+
+fn write_dataset(path: &Path, data: &Dataset) -> io::Result<()> {
+	match data {
+		Dataset::I32(v) => write_bin_le(path, v),
+		Dataset::I64(v) => write_bin_le(path, v),
+		Dataset::U64(v) => write_bin_le(path, v),
+		Dataset::F32(v) => write_bin_le(path, v),
+		Dataset::F64(v) => write_bin_le(path, v),
+		Dataset::Str(v) => write_bin_strings(path, v),
+	}
+}
+
+// path -> generator from an existing manifest. Manifests without a generator
+// column predate bench gen, so everything in them came from gen_datasets.py.
+fn read_generators(csv_path: &Path) -> HashMap<String, String> {
+	let Ok(content) = fs::read_to_string(csv_path) else {
+		return HashMap::new();
+	};
+	let mut lines = content.lines();
+	let header: Vec<&str> = lines.next().unwrap_or("").split(',').map(str::trim).collect();
+	let path_col = header.iter().position(|&c| c == "path");
+	let gen_col = header.iter().position(|&c| c == "generator");
+	let Some(path_col) = path_col else {
+		return HashMap::new();
+	};
+	lines
+		.filter_map(|line| {
+			let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+			let generator = gen_col.and_then(|c| fields.get(c).copied()).unwrap_or(PYTHON_GENERATOR);
+			Some((fields.get(path_col)?.to_string(), generator.to_string()))
+		})
+		.collect()
+}
+
+fn parse_list<T: std::str::FromStr>(v: &str) -> Vec<T> {
+	let items: Vec<T> = v
+		.split(',')
+		.map(str::trim)
+		.filter(|p| !p.is_empty())
+		.map(|p| p.parse().unwrap_or_else(|_| usage_and_exit()))
+		.collect();
+	if items.is_empty() {
+		usage_and_exit();
+	}
+	items
+}
+
+pub fn run(mut it: impl Iterator<Item = String>) -> io::Result<()> {
+	let mut outdir = "datasets/ints".to_string();
+	let mut meta_dir = "datasets/meta".to_string();
+	let mut sizes: Vec<usize> = DEFAULT_SIZES.to_vec();
+	let mut seeds: Vec<u64> = vec![1];
+	let mut dists: Option<String> = None;
+	let mut elem = ElemType::I32;
+	let mut force = false;
+	let mut dry_run = false;
+
+	while let Some(arg) = it.next() {
+		match arg.as_str() {
+			"--outdir" => outdir = it.next().unwrap_or_else(|| usage_and_exit()),
+			"--meta-dir" => meta_dir = it.next().unwrap_or_else(|| usage_and_exit()),
+			"--sizes" => sizes = parse_list(&it.next().unwrap_or_else(|| usage_and_exit())),
+			"--seeds" => seeds = parse_list(&it.next().unwrap_or_else(|| usage_and_exit())),
+			"--dists" => dists = Some(it.next().unwrap_or_else(|| usage_and_exit())),
+			"--elem-type" => elem = ElemType::parse(&it.next().unwrap_or_else(|| usage_and_exit())).unwrap_or_else(|| usage_and_exit()),
+			"--force" => force = true,
+			"--dry-run" => dry_run = true,
+			"-h" | "--help" => usage_and_exit(),
+			_ => {
+				eprintln!("Unknown arg: {}", arg);
+				usage_and_exit();
+			}
+		}
+	}
+
+	let dists = dists.unwrap_or_else(|| if elem == ElemType::Str { DEFAULT_STR_DISTS } else { DEFAULT_DISTS }.to_string());
+
+	// Fail before writing anything rather than halfway through.
+	let dists: Vec<String> = dists
+		.split(',')
+		.map(str::trim)
+		.filter(|d| !d.is_empty())
+		.map(|d| {
+			let canonical = match elem {
+				ElemType::I32 => canonical_dist(d),
+				_ => generate_as(elem, d, 0, 0).map(|_| d.to_string()),
+			};
+			canonical.unwrap_or_else(|e| {
+				eprintln!("{}", e);
+				std::process::exit(2);
+			})
+		})
+		.collect();
+	if dists.is_empty() {
+		eprintln!("No distributions provided.");
+		std::process::exit(2);
+	}
+	for algo in dists.iter().filter_map(|d| d.strip_prefix("antiqsort_")) {
+		for &n in &sizes {
+			if let Err(e) = antiqsort::check_size(algo, n) {
+				eprintln!("{}", e);
+				std::process::exit(2);
+			}
+		}
+	}
+
+	fs::create_dir_all(&outdir)?;
+	fs::create_dir_all(&meta_dir)?;
+
+	// datasets_<type>.csv for other types, so run_all.sh keeps seeing only i32 files.
+	let csv_name = match elem {
+		ElemType::I32 => "datasets.csv".to_string(),
+		_ => format!("datasets_{}.csv", elem.name()),
+	};
+	let csv_path = Path::new(&meta_dir).join(csv_name);
+	let csv_display = csv_path.to_string_lossy().replace('\\', "/");
+	let previous = read_generators(&csv_path);
+
+	let mut specs = Vec::new();
+	for dist in &dists {
+		for &n in &sizes {
+			for &seed in &seeds {
+				let path = Path::new(&outdir).join(dataset_filename(dist, elem, n, seed));
+				let rel_path = path.to_string_lossy().replace('\\', "/");
+				specs.push((dist, n, seed, path, rel_path));
+			}
+		}
+	}
+
+	// Our values differ from gen_datasets.py's under the same names, so
+	// replacing its files would silently change what every lane measures.
+	if force && !dry_run {
+		for (_, _, _, path, rel_path) in &specs {
+			if path.exists() && previous.get(rel_path).is_some_and(|g| g == PYTHON_GENERATOR) {
+				eprintln!(
+					"{} was written by {}; bench gen draws different values, so --force will not replace it (use another --outdir, or delete it first)",
+					rel_path, PYTHON_GENERATOR
+				);
+				std::process::exit(2);
+			}
+		}
+	}
+
+	let mut rows = Vec::new();
+	for (dist, n, seed, path, rel_path) in &specs {
+		// Files we skip keep the generator the old manifest recorded.
+		let mut generator = GENERATOR.to_string();
+		if dry_run {
+			println!("[dry-run] Would generate: {}", rel_path);
+		} else if path.exists() && !force {
+			println!("Skip existing (use --force to overwrite): {}", rel_path);
+			generator = previous.get(rel_path).cloned().unwrap_or_else(|| "unknown".to_string());
+		} else {
+			println!("Generating: {}", rel_path);
+			let data = generate_as(elem, dist, *n, *seed).expect("dists checked above");
+			write_dataset(path, &data)?;
+		}
+		rows.push(format!("{},{},{},{},{}", dist, n, seed, rel_path, generator));
+	}
+
+	if dry_run {
+		println!("[dry-run] Would write meta CSV: {}", csv_display);
+		return Ok(());
+	}
+
+	// CRLF like Python's csv module, so either generator yields the same manifest.
+	let mut f = BufWriter::new(File::create(&csv_path)?);
+	write!(f, "distribution,n,seed,path,generator\r\n")?;
+	for row in &rows {
+		write!(f, "{}\r\n", row)?;
+	}
+	f.flush()?;
+
+	println!("Wrote meta CSV: {}", csv_display);
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_name_splits_from_the_right() {
+		let cases = [
+			("random_n1000_seed1", Some(("random", 1000, 1))),
+			("nearly_sorted_n100000_seed42", Some(("nearly_sorted", 100000, 42))),
+			("few_unique_k16_n1000_seed1", Some(("few_unique_k16", 1000, 1))),
+			("sorted_runs_k1000_n1000000_seed3", Some(("sorted_runs_k1000", 1000000, 3))),
+			("sorted_random_tail_n10_seed1", Some(("sorted_random_tail", 10, 1))),
+			("antiqsort_quick_lomuto_n1000_seed1", Some(("antiqsort_quick_lomuto", 1000, 1))),
+			("random_n1000", None),
+			("random_seed1", None),
+			("random_nx_seed1", None),
+			("random_n1000_seedx", None),
+		];
+		for (stem, want) in cases {
+			assert_eq!(parse_name(stem), want, "{}", stem);
+		}
+	}
+
+	#[test]
+	fn canonical_dist_names() {
+		let cases = [
+			("random", Some("random")),
+			("sorted_random_tail", Some("sorted_random_tail")),
+			("few_unique", Some("few_unique_k16")),
+			("few_unique_k4", Some("few_unique_k4")),
+			("sorted_runs", Some("sorted_runs_k1000")),
+			("sorted_runs_k10", Some("sorted_runs_k10")),
+			("antiqsort_quick_lomuto", Some("antiqsort_quick_lomuto")),
+			("few_unique_k0", None),
+			("random_k4", None),
+			("antiqsort_radix_lsd", None),
+			("antiqsort_par_merge", None),
+			("antiqsort_nope", None),
+			("nope", None),
+		];
+		for (dist, want) in cases {
+			assert_eq!(canonical_dist(dist).ok().as_deref(), want, "{}", dist);
+		}
+	}
+}
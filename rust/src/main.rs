@@ -4,10 +4,37 @@ use std::io::{self, Read, Write};
 use std::path::Path;
 use std::time::Instant;
 
+mod alloc;
+mod antiqsort;
+mod elem;
+mod fingerprint;
+mod gen;
+mod manifest;
+mod perf;
+mod preflight;
+mod sched;
+mod sorters;
+mod tasks;
+
+use elem::{Elem, ElemType};
+use sorters::{GapSequence, SortConfig};
+use tasks::{Task, TaskElem, TaskOptions};
+
 fn usage_and_exit() -> ! {
 	eprintln!(
 		"Usage:
-  cargo run -- --dataset <path> [--algo builtin] [--warmup N] [--reps N] [--out <csv>] [--no-validate]
+  cargo run -- --dataset <path> [--algo <name>] [--warmup N] [--reps N] [--out <csv>] [--no-validate]
+               [--shell-gaps shell|knuth|sedgewick|ciura|<g1,g2,...>] [--max-quadratic-n N]
+               [--log-runs] [--threads N] [--threads-sweep 1,2,4,8] [--count-ops] [--perf]
+               [--track-alloc] [--pin-cpu N] [--priority] [--strict-env | --no-preflight]
+  cargo run -- --dataset <path> --task <name> [--algo <name>] [--k N | --quantile Q] [--bins N] [...]
+  cargo run -- --dataset <path> --task records [--algo value_stable|index_stable|...] [--payload-bytes 8|32|128] [...]
+  cargo run -- --dataset <path> --dataset-b <path> --task merge|intersection|union|difference [...]
+  cargo run -- --dataset <path>.<i64|u64|f32|f64|str>.bin [--elem-type i32|i64|u64|f32|f64|str] [...]   (sort only)
+  cargo run -- --dataset gen:<dist>_n<n>_seed<seed> [...]   (i32, generated in memory; dists: see gen --help)
+  cargo run -- gen [--dists ...] [--sizes ...] [--seeds ...] [...]   (writes datasets/ints, datasets/meta)
+  cargo run -- --list-algos
+  cargo run -- --list-tasks
 
 Example:
   cargo run -- --dataset ../datasets/ints/random_n100000_seed1.bin --warmup 5 --reps 30 --out ../results/raw.csv"
@@ -18,20 +45,53 @@ Example:
 #[derive(Clone)]
 struct Args {
 	dataset: String,
+	dataset_b: Option<String>,
+	elem_type: ElemType,
+	task: String,
 	algo: String,
 	warmup: usize,
 	reps: usize,
 	out: String,
 	validate: bool,
+	opts: TaskOptions,
+	threads_sweep: Option<Vec<usize>>,
+	perf: bool,
+	track_alloc: bool,
+	pin_cpu: Option<usize>,
+	priority: bool,
+	strict_env: bool,
+	preflight: bool,
+	// Filled in by main() once --pin-cpu/--priority have been applied
+	placement: sched::Placement,
+	// Filled in by main(); links each row to its line in the run manifest
+	run_id: String,
 }
 
 fn parse_args() -> Args {
 	let mut dataset: Option<String> = None;
+	let mut dataset_b: Option<String> = None;
+	let mut elem_type: Option<ElemType> = None;
+	let mut task = "sort".to_string();
 	let mut algo = "builtin".to_string();
 	let mut warmup: usize = 5;
 	let mut reps: usize = 30;
 	let mut out = "results/raw.csv".to_string();
 	let mut validate = true;
+	let mut sort_cfg = SortConfig::default();
+	let mut max_quadratic_n = tasks::DEFAULT_MAX_QUADRATIC_N;
+	let mut log_runs = false;
+	let mut threads_sweep: Option<Vec<usize>> = None;
+	let mut k: Option<usize> = None;
+	let mut quantile: Option<f64> = None;
+	let mut bins: usize = 256;
+	let mut payload_bytes: usize = 8;
+	let mut count_ops = false;
+	let mut perf = false;
+	let mut track_alloc = false;
+	let mut pin_cpu: Option<usize> = None;
+	let mut priority = false;
+	let mut strict_env = false;
+	let mut preflight = true;
 
 	let mut it = env::args().skip(1);
 	while let Some(arg) = it.next() {
@@ -42,6 +102,28 @@ fn parse_args() -> Args {
 					usage_and_exit();
 				}
 			}
+			"--dataset-b" => {
+				dataset_b = Some(it.next().unwrap_or_else(|| usage_and_exit()));
+			}
+			"--elem-type" => {
+				let v = it.next().unwrap_or_else(|| usage_and_exit());
+				elem_type = Some(ElemType::parse(&v).unwrap_or_else(|| usage_and_exit()));
+			}
+			"--task" => {
+				task = it.next().unwrap_or_else(|| usage_and_exit());
+			}
+			"--k" => {
+				k = Some(it.next().unwrap_or_else(|| usage_and_exit()).parse().unwrap_or_else(|_| usage_and_exit()));
+			}
+			"--quantile" => {
+				quantile = Some(it.next().unwrap_or_else(|| usage_and_exit()).parse().unwrap_or_else(|_| usage_and_exit()));
+			}
+			"--bins" => {
+				bins = it.next().unwrap_or_else(|| usage_and_exit()).parse().unwrap_or_else(|_| usage_and_exit());
+			}
+			"--payload-bytes" => {
+				payload_bytes = it.next().unwrap_or_else(|| usage_and_exit()).parse().unwrap_or_else(|_| usage_and_exit());
+			}
 			"--algo" => {
 				algo = it.next().unwrap_or_else(|| usage_and_exit());
 			}
@@ -57,6 +139,56 @@ fn parse_args() -> Args {
 			"--no-validate" => {
 				validate = false;
 			}
+			"--shell-gaps" => {
+				let v = it.next().unwrap_or_else(|| usage_and_exit());
+				sort_cfg.shell_gaps = GapSequence::parse(&v).unwrap_or_else(|| usage_and_exit());
+			}
+			"--max-quadratic-n" => {
+				max_quadratic_n = it.next().unwrap_or_else(|| usage_and_exit()).parse().unwrap_or_else(|_| usage_and_exit());
+			}
+			"--threads" => {
+				sort_cfg.threads = it.next().unwrap_or_else(|| usage_and_exit()).parse().unwrap_or_else(|_| usage_and_exit());
+			}
+			"--threads-sweep" => {
+				let v = it.next().unwrap_or_else(|| usage_and_exit());
+				let mut counts = Vec::new();
+				for part in v.split(',') {
+					counts.push(part.trim().parse::<usize>().unwrap_or_else(|_| usage_and_exit()));
+				}
+				threads_sweep = Some(counts);
+			}
+			"--log-runs" => {
+				log_runs = true;
+			}
+			"--count-ops" => {
+				count_ops = true;
+			}
+			"--perf" => {
+				perf = true;
+			}
+			"--track-alloc" => {
+				track_alloc = true;
+			}
+			"--pin-cpu" => {
+				pin_cpu = Some(it.next().unwrap_or_else(|| usage_and_exit()).parse().unwrap_or_else(|_| usage_and_exit()));
+			}
+			"--priority" => {
+				priority = true;
+			}
+			"--strict-env" => {
+				strict_env = true;
+			}
+			"--no-preflight" => {
+				preflight = false;
+			}
+			"--list-algos" => {
+				sorters::print_list();
+				std::process::exit(0);
+			}
+			"--list-tasks" => {
+				tasks::print_list();
+				std::process::exit(0);
+			}
 			_ => {
 				eprintln!("Unknown arg: {}", arg);
 				usage_and_exit();
@@ -65,27 +197,97 @@ fn parse_args() -> Args {
 	}
 
 	let dataset = dataset.unwrap_or_else(|| usage_and_exit());
+	let elem_type = elem_type.unwrap_or_else(|| ElemType::from_path(&dataset));
 
 	if warmup > 1_000_000 || reps == 0 {
 		eprintln!("warmup must be >= 0 and reps must be > 0");
 		std::process::exit(2);
 	}
-	if algo != "builtin" {
-		eprintln!("only --algo builtin is supported right now");
+	if sort_cfg.threads == 0 || threads_sweep.as_ref().is_some_and(|s| s.contains(&0)) {
+		eprintln!("threads must be > 0");
+		std::process::exit(2);
+	}
+	// Speedup is reported relative to 1 thread, so the sweep always starts there.
+	if let Some(sweep) = &mut threads_sweep {
+		sweep.sort_unstable();
+		sweep.dedup();
+		if sweep[0] != 1 {
+			eprintln!("--threads-sweep: adding 1 thread as the speedup baseline");
+			sweep.insert(0, 1);
+		}
+	}
+	if quantile.is_some_and(|q| !(0.0..=1.0).contains(&q)) {
+		eprintln!("quantile must be in [0, 1]");
+		std::process::exit(2);
+	}
+	let opts = TaskOptions { sort_cfg, max_quadratic_n, log_runs, k, quantile, bins, payload_bytes, count_ops };
+	let needs_dataset_b = match elem_type {
+		ElemType::I32 => tasks::build::<i32>(&task, &algo, &opts).map(|t| t.needs_dataset_b()),
+		ElemType::I64 => tasks::build::<i64>(&task, &algo, &opts).map(|t| t.needs_dataset_b()),
+		ElemType::U64 => tasks::build::<u64>(&task, &algo, &opts).map(|t| t.needs_dataset_b()),
+		ElemType::F32 => tasks::build::<f32>(&task, &algo, &opts).map(|t| t.needs_dataset_b()),
+		ElemType::F64 => tasks::build::<f64>(&task, &algo, &opts).map(|t| t.needs_dataset_b()),
+		ElemType::Str => tasks::build::<String>(&task, &algo, &opts).map(|t| t.needs_dataset_b()),
+	};
+	match needs_dataset_b {
+		Err(e) => {
+			eprintln!("{}", e);
+			std::process::exit(2);
+		}
+		Ok(needed) if needed != dataset_b.is_some() => {
+			if dataset_b.is_some() {
+				eprintln!("--dataset-b is not used by --task {}", task);
+			} else {
+				eprintln!("--task {} needs --dataset-b", task);
+			}
+			std::process::exit(2);
+		}
+		Ok(_) => {}
+	}
+	if strict_env && !preflight {
+		eprintln!("--strict-env needs the preflight checks; drop --no-preflight");
+		std::process::exit(2);
+	}
+	if threads_sweep.is_some() && task != "sort" {
+		eprintln!("--threads-sweep only applies to --task sort");
 		std::process::exit(2);
 	}
 
-	Args { dataset, algo, warmup, reps, out, validate }
+	Args {
+		dataset,
+		dataset_b,
+		elem_type,
+		task,
+		algo,
+		warmup,
+		reps,
+		out,
+		validate,
+		opts,
+		threads_sweep,
+		perf,
+		track_alloc,
+		pin_cpu,
+		priority,
+		strict_env,
+		preflight,
+		placement: sched::Placement::default(),
+		run_id: String::new(),
+	}
 }
 
 fn infer_distribution(dataset_path: &str) -> String {
-	let base = Path::new(dataset_path)
+	let base = Path::new(dataset_path.strip_prefix("gen:").unwrap_or(dataset_path))
 		.file_name()
 		.map(|s| s.to_string_lossy().to_string())
 		.unwrap_or_else(|| "unknown".to_string());
 
-	// expects: "<dist>_n<...>_seed<...>.bin"
-	base.split("_n").next().unwrap_or("unknown").to_string()
+	// expects: "<dist>_n<...>_seed<...>[.<type>].bin", where dist may contain "_n"
+	let stem = base.split('.').next().unwrap_or_default();
+	match gen::parse_name(stem) {
+		Some((dist, _, _)) => dist.to_string(),
+		None => base.split("_n").next().unwrap_or("unknown").to_string(),
+	}
 }
 
 fn now_iso_local() -> String {
@@ -106,6 +308,61 @@ fn file_exists(path: &str) -> bool {
 	Path::new(path).exists()
 }
 
+fn csv_header() -> Vec<&'static str> {
+	let mut header = vec![
+		"timestamp_iso",
+		"task",
+		"language",
+		"language_version",
+		"algo",
+		"dataset_file",
+		"distribution",
+		"n",
+		"warmup_runs",
+		"rep_idx",
+		"time_ms",
+		"ok",
+		"threads",
+		"k",
+		"dataset_b_file",
+		"elem_type",
+		"payload_bytes",
+		"comparisons",
+		"swaps",
+		"moves",
+	];
+	header.extend(perf::columns());
+	header.extend(["alloc_bytes", "alloc_count", "peak_alloc_bytes", "prepare_alloc_bytes", "peak_rss_kb", "pinned_cpu", "sched_policy", "run_id"]);
+	header
+}
+
+// Makes sure rows we append line up with the file's header. A file whose
+// header is a prefix of ours (the 12-column one the other languages share)
+// gets its header line extended in place; their rows simply leave the new
+// columns empty. Any other header is an error rather than a misaligned file.
+fn check_csv_header(csv_path: &str) -> io::Result<()> {
+	if !file_exists(csv_path) {
+		return Ok(());
+	}
+	let content = fs::read_to_string(csv_path)?;
+	let (first, rest) = content.split_once('\n').unwrap_or((&content, ""));
+	let existing: Vec<&str> = first.trim_end_matches('\r').split(',').collect();
+	let header = csv_header();
+	if existing == header {
+		return Ok(());
+	}
+	if existing.len() < header.len() && header.starts_with(&existing) {
+		eprintln!("note: extending the {}-column header of {} to {} columns", existing.len(), csv_path, header.len());
+		let tmp = format!("{}.tmp", csv_path);
+		fs::write(&tmp, format!("{}\n{}", header.join(","), rest))?;
+		return fs::rename(&tmp, csv_path);
+	}
+	Err(io::Error::new(
+		io::ErrorKind::InvalidData,
+		format!("{}: header does not match this bench's {} columns; pass a new --out", csv_path, header.len()),
+	))
+}
+
 fn append_row(csv_path: &str, row: &[String]) -> io::Result<()> {
 	ensure_parent_dir(csv_path)?;
 	let new_file = !file_exists(csv_path);
@@ -116,28 +373,15 @@ fn append_row(csv_path: &str, row: &[String]) -> io::Result<()> {
 		.open(csv_path)?;
 
 	if new_file {
-		let header = [
-			"timestamp_iso",
-			"task",
-			"language",
-			"language_version",
-			"algo",
-			"dataset_file",
-			"distribution",
-			"n",
-			"warmup_runs",
-			"rep_idx",
-			"time_ms",
-			"ok",
-		];
-		writeln!(f, "{}", header.join(","))?;
+		writeln!(f, "{}", csv_header().join(","))?;
 	}
 
 	writeln!(f, "{}", row.join(","))?;
 	Ok(())
 }
 
-fn read_bin_int32_le(path: &str) -> io::Result<Vec<i32>> {
+// u32 n followed by n little-endian T values; T = i32 is the original .bin format.
+fn read_bin_le<T: Elem>(path: &str) -> io::Result<Vec<T>> {
 	let mut f = fs::File::open(path)?;
 	let mut buf = Vec::new();
 	f.read_to_end(&mut buf)?;
@@ -147,83 +391,301 @@ fn read_bin_int32_le(path: &str) -> io::Result<Vec<i32>> {
 	}
 
 	let n = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
-	let expected = 4 + n * 4;
+	let expected = 4 + n * T::BYTES;
 	if buf.len() != expected {
 		return Err(io::Error::new(
 			io::ErrorKind::InvalidData,
-			format!("Expected {} bytes total, got {}", expected, buf.len()),
+			format!("Expected {} bytes total for {} x {}, got {}", expected, n, T::NAME, buf.len()),
 		));
 	}
 
-	let mut out = Vec::with_capacity(n);
-	let mut i = 4;
-	for _ in 0..n {
-		let v = i32::from_le_bytes([buf[i], buf[i + 1], buf[i + 2], buf[i + 3]]);
-		out.push(v);
-		i += 4;
+	Ok(buf[4..].chunks_exact(T::BYTES).map(T::from_le).collect())
+}
+
+// u32 n followed by n strings, each a u32 byte length and that many bytes of UTF-8.
+fn read_bin_strings(path: &str) -> io::Result<Vec<String>> {
+	let mut f = fs::File::open(path)?;
+	let mut buf = Vec::new();
+	f.read_to_end(&mut buf)?;
+
+	let truncated = || io::Error::new(io::ErrorKind::InvalidData, "File truncated");
+	let read_u32 = |pos: usize| -> io::Result<usize> {
+		let b = buf.get(pos..pos + 4).ok_or_else(truncated)?;
+		Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+	};
+
+	let n = read_u32(0)?;
+	let mut pos = 4;
+	let mut out = Vec::with_capacity(n.min(buf.len() / 4));
+	for i in 0..n {
+		let len = read_u32(pos)?;
+		pos += 4;
+		let bytes = buf.get(pos..pos + len).ok_or_else(truncated)?;
+		let s = std::str::from_utf8(bytes)
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("String {} is not UTF-8: {}", i, e)))?;
+		out.push(s.to_string());
+		pos += len;
+	}
+	if pos != buf.len() {
+		return Err(io::Error::new(
+			io::ErrorKind::InvalidData,
+			format!("{} trailing bytes after {} strings", buf.len() - pos, n),
+		));
 	}
+
 	Ok(out)
 }
 
-fn is_sorted_non_decreasing(a: &[i32]) -> bool {
+// i32 datasets may also be "gen:<dist>_n<n>_seed<seed>", generated in memory.
+fn read_i32_dataset(path: &str) -> io::Result<Vec<i32>> {
+	match path.strip_prefix("gen:") {
+		Some(name) => gen::generate_named(name).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e)),
+		None => read_bin_le(path),
+	}
+}
+
+pub(crate) fn is_sorted_non_decreasing<T: Ord>(a: &[T]) -> bool {
 	a.windows(2).all(|w| w[0] <= w[1])
 }
 
 fn rust_version() -> String {
-	// No stable std API to query rustc version at runtime without external crates/build scripts.
-	// We'll output "rust" + package version. Good enough for benchmarks, and you can record rustc in env_info.
-	format!("rust/{}", env!("CARGO_PKG_VERSION"))
+	// rustc release captured by build.rs; the full version string and target
+	// triple go into the run manifest.
+	fingerprint::RUSTC_RELEASE.to_string()
 }
 
-fn main() -> io::Result<()> {
-	let args = parse_args();
+// What one timed run() produced.
+struct Sample {
+	time_ms: f64,
+	perf: Option<perf::PerfReadings>,
+	alloc: Option<alloc::AllocStats>,
+	// What the untimed prepare() before this rep allocated (the input copy)
+	prepare_alloc: Option<alloc::AllocStats>,
+}
+
+// One results row; `label` is the task name, or "<task>_<phase>" for multi-phase tasks.
+fn make_row<T>(args: &Args, task: &dyn Task<T>, label: &str, n: usize, rep: usize, sample: &Sample, ok: bool) -> Vec<String> {
+	let counts = task.op_counts();
+	let mut row = vec![
+		now_iso_local(),
+		label.to_string(),
+		"rust".to_string(),
+		rust_version(),
+		args.algo.clone(),
+		args.dataset.clone(),
+		infer_distribution(&args.dataset),
+		n.to_string(),
+		args.warmup.to_string(),
+		rep.to_string(),
+		format!("{:.3}", sample.time_ms),
+		if ok { "true".to_string() } else { "false".to_string() },
+		task.threads().to_string(),
+		task.k().map(|k| k.to_string()).unwrap_or_default(),
+		args.dataset_b.clone().unwrap_or_default(),
+		args.elem_type.name().to_string(),
+		task.payload_bytes().map(|b| b.to_string()).unwrap_or_default(),
+		counts.map(|c| c.comparisons.to_string()).unwrap_or_default(),
+		counts.and_then(|c| c.swaps).map(|s| s.to_string()).unwrap_or_default(),
+		counts.and_then(|c| c.moves).map(|m| m.to_string()).unwrap_or_default(),
+	];
+	for i in 0..perf::columns().len() {
+		row.push(sample.perf.as_ref().and_then(|p| p[i]).map(|v| v.to_string()).unwrap_or_default());
+	}
+	let alloc_col = |f: fn(&alloc::AllocStats) -> u64| sample.alloc.as_ref().map(|a| f(a).to_string()).unwrap_or_default();
+	row.push(alloc_col(|a| a.bytes));
+	row.push(alloc_col(|a| a.count));
+	row.push(alloc_col(|a| a.peak_bytes));
+	row.push(sample.prepare_alloc.map(|a| a.bytes.to_string()).unwrap_or_default());
+	row.push(if args.track_alloc { alloc::peak_rss_kb().map(|r| r.to_string()).unwrap_or_default() } else { String::new() });
+	row.push(args.placement.cpu.map(|c| c.to_string()).unwrap_or_default());
+	row.push(args.placement.policy.clone());
+	row.push(args.run_id.clone());
+	row
+}
 
-	let values = read_bin_int32_le(&args.dataset)?;
+// Setup, warmup and measured reps for one task; writes a CSV row per rep
+// (per phase, for multi-phase tasks) and returns the measured time of each rep.
+fn run_task<T>(args: &Args, task: &mut dyn Task<T>, values: &[T], values_b: Option<&[T]>) -> io::Result<Vec<f64>> {
 	let n = values.len();
-	let dist = infer_distribution(&args.dataset);
 
-	let lang = "rust".to_string();
-	let lang_ver = rust_version();
+	if let Some(b) = values_b {
+		task.setup_b(b);
+	}
+	if let Err(e) = task.setup(values) {
+		eprintln!("{}", e);
+		std::process::exit(2);
+	}
+
+	let labels: Vec<String> = if task.phases().is_empty() {
+		vec![args.task.clone()]
+	} else {
+		task.phases().iter().map(|p| format!("{}_{}", args.task, p)).collect()
+	};
 
 	// Warmup
 	for _ in 0..args.warmup {
-		let mut tmp = values.clone();
-		tmp.sort_unstable();
+		task.prepare();
+		for phase in 0..labels.len() {
+			task.run(phase);
+		}
 	}
 
 	// Measured
+	let mut counters = args.perf.then(perf::PerfCounters::open);
+	let mut times = Vec::with_capacity(args.reps);
 	for rep in 0..args.reps {
-		let mut tmp = values.clone();
-
-		let t0 = Instant::now();
-		tmp.sort_unstable();
-		let elapsed = t0.elapsed();
-		let time_ms = (elapsed.as_nanos() as f64) / 1_000_000.0;
-
-		let ok = if args.validate {
-			is_sorted_non_decreasing(&tmp)
-		} else {
-			true
-		};
-
-		let row = vec![
-			now_iso_local(),
-			"sort".to_string(),
-			lang.clone(),
-			lang_ver.clone(),
-			args.algo.clone(),
-			args.dataset.clone(),
-			dist.clone(),
-			n.to_string(),
-			args.warmup.to_string(),
-			rep.to_string(),
-			format!("{:.3}", time_ms),
-			if ok { "true".to_string() } else { "false".to_string() },
-		];
+		if args.track_alloc {
+			alloc::start();
+		}
+		task.prepare();
+		let prepare_alloc = args.track_alloc.then(alloc::stop);
+
+		let mut samples = Vec::with_capacity(labels.len());
+		for phase in 0..labels.len() {
+			if let Some(c) = &mut counters {
+				c.start();
+			}
+			if args.track_alloc {
+				alloc::start();
+			}
+			let t0 = Instant::now();
+			task.run(phase);
+			let elapsed = t0.elapsed();
+			let alloc = args.track_alloc.then(alloc::stop);
+			let perf = counters.as_mut().map(|c| c.stop());
+			samples.push(Sample { time_ms: (elapsed.as_nanos() as f64) / 1_000_000.0, perf, alloc, prepare_alloc });
+		}
+		times.push(samples.iter().map(|s| s.time_ms).sum());
+
+		task.after_rep(rep);
+
+		let ok = if args.validate { task.validate() } else { true };
+
+		for (label, sample) in labels.iter().zip(&samples) {
+			let row = make_row(args, task, label, n, rep, sample, ok);
+			println!("{}", row.join(","));
+			append_row(&args.out, &row)?;
+		}
+	}
+
+	Ok(times)
+}
+
+fn median(times: &[f64]) -> f64 {
+	let mut t = times.to_vec();
+	t.sort_by(|a, b| a.total_cmp(b));
+	let mid = t.len() / 2;
+	if t.len().is_multiple_of(2) {
+		(t[mid - 1] + t[mid]) / 2.0
+	} else {
+		t[mid]
+	}
+}
+
+fn main() -> io::Result<()> {
+	if env::args().nth(1).as_deref() == Some("gen") {
+		return gen::run(env::args().skip(2));
+	}
+	let mut args = parse_args();
+	if args.track_alloc {
+		alloc::enable();
+	}
+	let checks = if args.preflight { preflight::run() } else { Vec::new() };
+	let warnings = preflight::report(&checks);
+	args.placement = sched::apply(args.pin_cpu, args.priority);
+	let max_threads = args.threads_sweep.as_ref().and_then(|s| s.iter().max().copied()).unwrap_or(args.opts.sort_cfg.threads);
+	if args.placement.cpu.is_some() && max_threads > 1 {
+		eprintln!("warning: --pin-cpu puts all {} threads on one CPU", max_threads);
+	}
+	args.run_id = format!("{}-{}", chrono::Local::now().format("%Y%m%dT%H%M%S"), std::process::id());
+	let mut run = manifest::JsonObject::default();
+	run.str("run_id", &args.run_id)
+		.str("started", &now_iso_local())
+		.strs("command", &env::args().collect::<Vec<_>>())
+		.str("csv", &args.out)
+		.obj("build", &manifest::build_json())
+		.obj("host", &manifest::host_json(&fingerprint::collect()))
+		.obj("placement", &manifest::placement_json(&args.placement))
+		.objs("preflight", &preflight::to_json(&checks))
+		.raw("preflight_run", args.preflight.to_string())
+		.raw("strict_env", args.strict_env.to_string());
+	check_csv_header(&args.out)?;
+	manifest::append(&args.out, &run)?;
+	if args.strict_env && warnings > 0 {
+		eprintln!("--strict-env: {} preflight check(s) failed; not measuring", warnings);
+		std::process::exit(2);
+	}
+	match args.elem_type {
+		ElemType::I32 => bench::<i32>(&args, read_i32_dataset),
+		ElemType::I64 => bench::<i64>(&args, read_bin_le),
+		ElemType::U64 => bench::<u64>(&args, read_bin_le),
+		ElemType::F32 => bench::<f32>(&args, read_bin_le),
+		ElemType::F64 => bench::<f64>(&args, read_bin_le),
+		ElemType::Str => bench::<String>(&args, read_bin_strings),
+	}
+}
 
-		println!("{}", row.join(","));
-		append_row(&args.out, &row)?;
+fn bench<T: TaskElem>(args: &Args, load: fn(&str) -> io::Result<Vec<T>>) -> io::Result<()> {
+	// Loaded once; a sweep reuses it for every thread count.
+	let values = load(&args.dataset)?;
+	let values_b = match &args.dataset_b {
+		Some(path) => Some(load(path)?),
+		None => None,
+	};
+
+	let Some(sweep) = &args.threads_sweep else {
+		let mut task = tasks::build::<T>(&args.task, &args.algo, &args.opts).expect("task checked in parse_args");
+		run_task(args, task.as_mut(), &values, values_b.as_deref())?;
+		return Ok(());
+	};
+
+	if !tasks::build::<T>(&args.task, &args.algo, &args.opts).expect("task checked in parse_args").is_parallel() {
+		eprintln!("--threads-sweep needs a parallel algo; {} ignores --threads", args.algo);
+		std::process::exit(2);
+	}
+
+	let mut medians = Vec::with_capacity(sweep.len());
+	for &threads in sweep {
+		let opts = TaskOptions { sort_cfg: SortConfig { threads, ..args.opts.sort_cfg.clone() }, ..args.opts.clone() };
+		let mut task = tasks::build::<T>(&args.task, &args.algo, &opts).expect("task checked in parse_args");
+		let times = run_task(args, task.as_mut(), &values, values_b.as_deref())?;
+		medians.push((threads, median(&times)));
+	}
+
+	// parse_args puts 1 first in the sweep
+	let base = medians[0].1;
+	eprintln!("threads,median_ms,speedup,efficiency");
+	for &(threads, med) in &medians {
+		let speedup = base / med;
+		eprintln!("{},{:.3},{:.2},{:.2}", threads, med, speedup, speedup / threads as f64);
 	}
 
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn infer_distribution_from_paths() {
+		let cases = [
+			("../datasets/ints/random_n1000_seed1.bin", "random"),
+			("datasets/ints/nearly_sorted_n100000_seed2.bin", "nearly_sorted"),
+			("datasets/i64/dups_n1000_seed1.i64.bin", "dups"),
+			("datasets/f64/specials_n1000_seed1.f64.bin", "specials"),
+			("datasets/str/shared_prefix_n1000_seed1.str.bin", "shared_prefix"),
+			("few_unique_k16_n1000_seed1.bin", "few_unique_k16"),
+			("sorted_runs_k1000_n1000000_seed1.bin", "sorted_runs_k1000"),
+			("antiqsort_quick_lomuto_n1000_seed1.bin", "antiqsort_quick_lomuto"),
+			("gen:sorted_random_tail_n1000_seed1", "sorted_random_tail"),
+			("gen:few_unique_k4_n100_seed7", "few_unique_k4"),
+			// Not a generated name: everything before the first "_n"
+			("custom_numbers.bin", "custom"),
+			("data.bin", "data.bin"),
+		];
+		for (path, want) in cases {
+			assert_eq!(infer_distribution(path), want, "{}", path);
+		}
+	}
+}
@@ -0,0 +1,126 @@
+// The run manifest: one JSON object per bench invocation, appended to a
+// .manifest.jsonl file next to the results CSV. Every CSV row carries the
+// run_id of the manifest line that describes it.
+
+use std::fmt::Write as _;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::fingerprint::{self, Fingerprint};
+use crate::sched::Placement;
+
+// A JSON object built field by field, in insertion order.
+#[derive(Default)]
+pub struct JsonObject {
+	fields: Vec<(String, String)>,
+}
+
+pub fn json_string(s: &str) -> String {
+	let mut out = String::with_capacity(s.len() + 2);
+	out.push('"');
+	for c in s.chars() {
+		match c {
+			'"' => out.push_str("\\\""),
+			'\\' => out.push_str("\\\\"),
+			'\n' => out.push_str("\\n"),
+			'\r' => out.push_str("\\r"),
+			'\t' => out.push_str("\\t"),
+			c if (c as u32) < 0x20 => {
+				let _ = write!(out, "\\u{:04x}", c as u32);
+			}
+			c => out.push(c),
+		}
+	}
+	out.push('"');
+	out
+}
+
+impl JsonObject {
+	pub fn raw(&mut self, key: &str, json: String) -> &mut Self {
+		self.fields.push((key.to_string(), json));
+		self
+	}
+
+	pub fn str(&mut self, key: &str, v: &str) -> &mut Self {
+		self.raw(key, json_string(v))
+	}
+
+	pub fn opt_str(&mut self, key: &str, v: Option<&str>) -> &mut Self {
+		self.raw(key, v.map(json_string).unwrap_or_else(|| "null".to_string()))
+	}
+
+	pub fn opt_num<N: ToString>(&mut self, key: &str, v: Option<N>) -> &mut Self {
+		self.raw(key, v.map(|n| n.to_string()).unwrap_or_else(|| "null".to_string()))
+	}
+
+	pub fn opt_bool(&mut self, key: &str, v: Option<bool>) -> &mut Self {
+		self.opt_num(key, v)
+	}
+
+	pub fn strs(&mut self, key: &str, v: &[String]) -> &mut Self {
+		let items: Vec<String> = v.iter().map(|s| json_string(s)).collect();
+		self.raw(key, format!("[{}]", items.join(",")))
+	}
+
+	pub fn obj(&mut self, key: &str, v: &JsonObject) -> &mut Self {
+		self.raw(key, v.render())
+	}
+
+	pub fn objs(&mut self, key: &str, v: &[JsonObject]) -> &mut Self {
+		let items: Vec<String> = v.iter().map(JsonObject::render).collect();
+		self.raw(key, format!("[{}]", items.join(",")))
+	}
+
+	pub fn render(&self) -> String {
+		let fields: Vec<String> = self.fields.iter().map(|(k, v)| format!("{}:{}", json_string(k), v)).collect();
+		format!("{{{}}}", fields.join(","))
+	}
+}
+
+// results/raw.csv -> results/raw.manifest.jsonl
+pub fn manifest_path(csv_path: &str) -> String {
+	let stem = csv_path.strip_suffix(".csv").unwrap_or(csv_path);
+	format!("{}.manifest.jsonl", stem)
+}
+
+pub fn host_json(fp: &Fingerprint) -> JsonObject {
+	let mut host = JsonObject::default();
+	host.opt_str("hostname", fp.hostname.as_deref())
+		.opt_str("kernel", fp.kernel.as_deref())
+		.opt_str("cpu_model", fp.cpu_model.as_deref())
+		.opt_num("logical_cpus", fp.logical_cpus)
+		.opt_num("physical_cores", fp.physical_cores)
+		.opt_num("available_parallelism", fp.available_parallelism)
+		.opt_str("governor", fp.governor.as_deref())
+		.opt_bool("turbo", fp.turbo)
+		.strs("cpu_flags", &fp.cpu_flags);
+	host
+}
+
+pub fn build_json() -> JsonObject {
+	let mut build = JsonObject::default();
+	build
+		.str("bench_version", env!("CARGO_PKG_VERSION"))
+		.str("rustc", fingerprint::RUSTC_VERSION)
+		.str("target", fingerprint::TARGET)
+		.str("profile", fingerprint::PROFILE);
+	build
+}
+
+pub fn placement_json(p: &Placement) -> JsonObject {
+	let mut placement = JsonObject::default();
+	placement.opt_num("pinned_cpu", p.cpu).str("sched_policy", &p.policy);
+	placement
+}
+
+pub fn append(csv_path: &str, manifest: &JsonObject) -> io::Result<()> {
+	let path = manifest_path(csv_path);
+	if let Some(parent) = Path::new(&path).parent() {
+		if !parent.as_os_str().is_empty() {
+			std::fs::create_dir_all(parent)?;
+		}
+	}
+	let mut f = OpenOptions::new().create(true).append(true).open(&path)?;
+	writeln!(f, "{}", manifest.render())
+}
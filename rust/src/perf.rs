@@ -0,0 +1,197 @@
+// --perf: Linux perf_event_open counters around each timed run(). The events
+// form small groups (user space only, inherited by threads the sort spawns);
+// the kernel schedules a group all or nothing, so each hardware group is kept
+// small enough to fit the PMU next to an NMI watchdog, and the software
+// events get a group of their own that always runs. Events the kernel
+// refuses, such as hardware counters inside most VMs, are reported as empty
+// columns. Context switches happen in kernel mode, which a user-space-only
+// event never sees, so they come from getrusage instead.
+
+use std::fs::File;
+use std::io::Read;
+use std::os::fd::{AsRawFd, FromRawFd};
+
+const PERF_TYPE_HARDWARE: u32 = 0;
+const PERF_TYPE_SOFTWARE: u32 = 1;
+const PERF_TYPE_HW_CACHE: u32 = 3;
+
+// L1D | (OP_READ << 8) | (RESULT_MISS << 16)
+const L1D_READ_MISS: u64 = 1 << 16;
+
+// (CSV column, type, config, group)
+pub const EVENTS: &[(&str, u32, u64, usize)] = &[
+	("cycles", PERF_TYPE_HARDWARE, 0, 0),
+	("instructions", PERF_TYPE_HARDWARE, 1, 0),
+	("cache_references", PERF_TYPE_HARDWARE, 2, 1),
+	("cache_misses", PERF_TYPE_HARDWARE, 3, 1),
+	("branch_misses", PERF_TYPE_HARDWARE, 5, 0),
+	("l1d_read_misses", PERF_TYPE_HW_CACHE, L1D_READ_MISS, 1),
+	("task_clock_ns", PERF_TYPE_SOFTWARE, 1, 2),
+	("page_faults", PERF_TYPE_SOFTWARE, 2, 2),
+];
+const GROUPS: usize = 3;
+
+// CSV columns: one per EVENTS entry, then the getrusage ones.
+pub fn columns() -> Vec<&'static str> {
+	let mut cols: Vec<&str> = EVENTS.iter().map(|e| e.0).collect();
+	cols.push("context_switches");
+	cols
+}
+
+const PERF_FORMAT_TOTAL_TIME_ENABLED: u64 = 1 << 0;
+const PERF_FORMAT_TOTAL_TIME_RUNNING: u64 = 1 << 1;
+
+const FLAG_DISABLED: u64 = 1 << 0;
+const FLAG_INHERIT: u64 = 1 << 1;
+const FLAG_EXCLUDE_KERNEL: u64 = 1 << 5;
+const FLAG_EXCLUDE_HV: u64 = 1 << 6;
+
+const PERF_EVENT_IOC_ENABLE: libc::c_ulong = 0x2400;
+const PERF_EVENT_IOC_DISABLE: libc::c_ulong = 0x2401;
+const PERF_EVENT_IOC_RESET: libc::c_ulong = 0x2403;
+const PERF_IOC_FLAG_GROUP: libc::c_ulong = 1;
+
+// struct perf_event_attr up to PERF_ATTR_SIZE_VER5; the bitfield flags are
+// one u64.
+#[repr(C)]
+#[derive(Default)]
+struct PerfEventAttr {
+	type_: u32,
+	size: u32,
+	config: u64,
+	sample_period: u64,
+	sample_type: u64,
+	read_format: u64,
+	flags: u64,
+	wakeup_events: u32,
+	bp_type: u32,
+	config1: u64,
+	config2: u64,
+	branch_sample_type: u64,
+	sample_regs_user: u64,
+	sample_stack_user: u32,
+	clockid: i32,
+	sample_regs_intr: u64,
+	aux_watermark: u32,
+	sample_max_stack: u16,
+	reserved_2: u16,
+}
+
+#[cfg(target_os = "linux")]
+fn perf_event_open(type_: u32, config: u64, group: Option<&File>) -> Result<File, std::io::Error> {
+	// Only the leader starts disabled; members follow it.
+	let disabled = if group.is_none() { FLAG_DISABLED } else { 0 };
+	let attr = PerfEventAttr {
+		type_,
+		size: std::mem::size_of::<PerfEventAttr>() as u32,
+		config,
+		read_format: PERF_FORMAT_TOTAL_TIME_ENABLED | PERF_FORMAT_TOTAL_TIME_RUNNING,
+		flags: disabled | FLAG_INHERIT | FLAG_EXCLUDE_KERNEL | FLAG_EXCLUDE_HV,
+		..Default::default()
+	};
+	// pid 0, cpu -1: this process (and its threads) on any CPU; no flags.
+	let group_fd = group.map_or(-1, |g| g.as_raw_fd());
+	let fd = unsafe { libc::syscall(libc::SYS_perf_event_open, &attr as *const PerfEventAttr, 0, -1, group_fd, 0) };
+	if fd < 0 {
+		return Err(std::io::Error::last_os_error());
+	}
+	Ok(unsafe { File::from_raw_fd(fd as i32) })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn perf_event_open(_type: u32, _config: u64, _group: Option<&File>) -> Result<File, std::io::Error> {
+	Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "perf_event_open is Linux-only"))
+}
+
+// One reading per columns() entry; None where the event could not be opened.
+pub type PerfReadings = Vec<Option<u64>>;
+
+pub struct PerfCounters {
+	fds: Vec<Option<File>>,
+	// Per group, the index into fds of its leader: the first of its events that opened
+	leaders: [Option<usize>; GROUPS],
+	csw_start: Option<u64>,
+}
+
+// Voluntary + involuntary context switches of the whole process so far.
+#[cfg(unix)]
+fn context_switches() -> Option<u64> {
+	let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+	if unsafe { libc::getrusage(libc::RUSAGE_SELF, &mut usage) } != 0 {
+		return None;
+	}
+	Some((usage.ru_nvcsw + usage.ru_nivcsw) as u64)
+}
+
+#[cfg(not(unix))]
+fn context_switches() -> Option<u64> {
+	None
+}
+
+impl PerfCounters {
+	pub fn open() -> PerfCounters {
+		let mut fds: Vec<Option<File>> = Vec::with_capacity(EVENTS.len());
+		let mut leaders = [None; GROUPS];
+		let mut refused = Vec::new();
+		for &(name, type_, config, g) in EVENTS {
+			let leader = leaders[g].and_then(|l: usize| fds[l].as_ref());
+			match perf_event_open(type_, config, leader) {
+				Ok(f) => {
+					leaders[g].get_or_insert(fds.len());
+					fds.push(Some(f));
+				}
+				Err(e) => {
+					refused.push(format!("{} ({})", name, e));
+					fds.push(None);
+				}
+			}
+		}
+		if !refused.is_empty() {
+			eprintln!("--perf: unavailable, left empty: {}", refused.join(", "));
+		}
+		PerfCounters { fds, leaders, csw_start: None }
+	}
+
+	// One ioctl on a leader applies to its whole group at once.
+	fn ioctl_groups(&self, request: libc::c_ulong) {
+		for f in self.leaders.iter().flatten().filter_map(|&l| self.fds[l].as_ref()) {
+			unsafe {
+				libc::ioctl(f.as_raw_fd(), request as _, PERF_IOC_FLAG_GROUP);
+			}
+		}
+	}
+
+	// Reset and enable every counter; call right before the timed region.
+	pub fn start(&mut self) {
+		self.csw_start = context_switches();
+		self.ioctl_groups(PERF_EVENT_IOC_RESET);
+		self.ioctl_groups(PERF_EVENT_IOC_ENABLE);
+	}
+
+	// Disable every counter and read it, scaled up if the kernel had to
+	// multiplex a group with other events. A group that never got onto the
+	// PMU (more hardware events than counters) reads as empty, not 0.
+	pub fn stop(&mut self) -> PerfReadings {
+		self.ioctl_groups(PERF_EVENT_IOC_DISABLE);
+		let csw_end = context_switches();
+		let mut readings: PerfReadings = self
+			.fds
+			.iter_mut()
+			.map(|f| {
+				let mut buf = [0u8; 24];
+				f.as_mut()?.read_exact(&mut buf).ok()?;
+				let word = |i: usize| u64::from_ne_bytes(buf[i * 8..i * 8 + 8].try_into().unwrap());
+				let (value, enabled, running) = (word(0), word(1), word(2));
+				if running == 0 {
+					(enabled == 0).then_some(value)
+				} else if running == enabled {
+					Some(value)
+				} else {
+					Some((value as f64 * enabled as f64 / running as f64) as u64)
+				}
+			})
+			.collect();
+		readings.push(self.csw_start.zip(csw_end).map(|(a, b)| b - a));
+		readings
+	}
+}
@@ -0,0 +1,194 @@
+// Preflight noise checks, run before anything is measured: frequency scaling,
+// turbo, power source, ASLR, load and busy processes. Failed checks are
+// warnings, or abort the run under --strict-env; every result goes into the
+// run manifest either way. --no-preflight skips them.
+
+use std::collections::HashMap;
+use std::fs;
+use std::thread;
+use std::time::Duration;
+
+use crate::fingerprint;
+use crate::manifest::JsonObject;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+	Pass,
+	Warn,
+	// Recorded for the manifest; neither good nor bad
+	Info,
+	// Not exposed here (no cpufreq in a VM, not Linux, ...)
+	Unknown,
+}
+
+impl Status {
+	fn name(self) -> &'static str {
+		match self {
+			Status::Pass => "pass",
+			Status::Warn => "warn",
+			Status::Info => "info",
+			Status::Unknown => "unknown",
+		}
+	}
+}
+
+pub struct Check {
+	pub name: &'static str,
+	pub status: Status,
+	pub detail: String,
+}
+
+fn check(name: &'static str, status: Status, detail: impl Into<String>) -> Check {
+	Check { name, status, detail: detail.into() }
+}
+
+// 1-minute load per usable CPU above which other jobs compete with us (never
+// below 1.0 in total, so a small box is not flagged for its own background).
+const MAX_LOAD1_PER_CPU: f64 = 0.5;
+// A process using more than this share of one CPU during the sample is busy.
+const BUSY_SHARE: f64 = 0.1;
+const BUSY_SAMPLE: Duration = Duration::from_millis(250);
+
+fn read_trimmed(path: &str) -> Option<String> {
+	fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+fn check_governor() -> Check {
+	match fingerprint::governor() {
+		None => check("governor", Status::Unknown, "no cpufreq scaling_governor"),
+		Some(g) if g == "performance" => check("governor", Status::Pass, g),
+		Some(g) => check("governor", Status::Warn, format!("{} (want performance)", g)),
+	}
+}
+
+fn check_turbo() -> Check {
+	match fingerprint::turbo() {
+		None => check("turbo", Status::Unknown, "no intel_pstate/no_turbo or cpufreq/boost"),
+		Some(false) => check("turbo", Status::Pass, "disabled"),
+		Some(true) => check("turbo", Status::Warn, "enabled (clock depends on temperature and other cores)"),
+	}
+}
+
+fn check_power() -> Check {
+	let Ok(dir) = fs::read_dir("/sys/class/power_supply") else {
+		return check("power", Status::Unknown, "no /sys/class/power_supply");
+	};
+	let mut on_mains = None;
+	let mut discharging = false;
+	for entry in dir.flatten() {
+		let base = entry.path();
+		let field = |f: &str| read_trimmed(&base.join(f).to_string_lossy());
+		match field("type").as_deref() {
+			Some("Mains") => on_mains = Some(on_mains.unwrap_or(false) || field("online").as_deref() == Some("1")),
+			Some("Battery") => discharging |= field("status").as_deref() == Some("Discharging"),
+			_ => {}
+		}
+	}
+	if discharging || on_mains == Some(false) {
+		check("power", Status::Warn, "on battery")
+	} else if on_mains == Some(true) {
+		check("power", Status::Pass, "on mains")
+	} else {
+		check("power", Status::Pass, "no battery")
+	}
+}
+
+fn check_aslr() -> Check {
+	match read_trimmed("/proc/sys/kernel/randomize_va_space").as_deref() {
+		Some("0") => check("aslr", Status::Info, "off"),
+		Some(v) => check("aslr", Status::Info, format!("on (randomize_va_space={})", v)),
+		None => check("aslr", Status::Unknown, "no /proc/sys/kernel/randomize_va_space"),
+	}
+}
+
+fn check_load() -> Check {
+	let Some(load1) = read_trimmed("/proc/loadavg").and_then(|l| l.split_whitespace().next()?.parse::<f64>().ok()) else {
+		return check("load", Status::Unknown, "no /proc/loadavg");
+	};
+	// Taken before --pin-cpu narrows our own affinity mask.
+	let cpus = std::thread::available_parallelism().map_or(1, |n| n.get());
+	let max_load = (cpus as f64 * MAX_LOAD1_PER_CPU).max(1.0);
+	let status = if load1 > max_load { Status::Warn } else { Status::Pass };
+	check("load", status, format!("1-minute load {:.2} (max {:.2} for {} CPUs)", load1, max_load, cpus))
+}
+
+// utime + stime in clock ticks for every process, keyed by pid.
+fn cpu_ticks() -> HashMap<u32, (String, u64)> {
+	let mut ticks = HashMap::new();
+	let Ok(dir) = fs::read_dir("/proc") else {
+		return ticks;
+	};
+	for entry in dir.flatten() {
+		let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse::<u32>().ok()) else {
+			continue;
+		};
+		let Ok(stat) = fs::read_to_string(format!("/proc/{}/stat", pid)) else {
+			continue;
+		};
+		// "pid (comm) state ..."; comm may itself contain spaces or parens.
+		let (Some(open), Some(close)) = (stat.find('('), stat.rfind(')')) else {
+			continue;
+		};
+		let rest: Vec<&str> = stat[close + 1..].split_whitespace().collect();
+		// Fields 14 and 15 of stat; rest starts at field 3.
+		let (Some(utime), Some(stime)) = (rest.get(11), rest.get(12)) else {
+			continue;
+		};
+		if let (Ok(u), Ok(s)) = (utime.parse::<u64>(), stime.parse::<u64>()) {
+			ticks.insert(pid, (stat[open + 1..close].to_string(), u + s));
+		}
+	}
+	ticks
+}
+
+fn check_busy_processes() -> Check {
+	let before = cpu_ticks();
+	if before.is_empty() {
+		return check("busy_processes", Status::Unknown, "no /proc/<pid>/stat");
+	}
+	thread::sleep(BUSY_SAMPLE);
+	let after = cpu_ticks();
+
+	let hz = unsafe { libc::sysconf(libc::_SC_CLK_TCK) }.max(1) as f64;
+	let me = std::process::id();
+	let mut busy: Vec<(f64, String)> = after
+		.iter()
+		.filter(|(pid, _)| **pid != me)
+		.filter_map(|(pid, (comm, t1))| {
+			let (_, t0) = before.get(pid)?;
+			let share = t1.saturating_sub(*t0) as f64 / hz / BUSY_SAMPLE.as_secs_f64();
+			(share > BUSY_SHARE).then(|| (share, format!("{}[{}] {:.0}%", comm, pid, share * 100.0)))
+		})
+		.collect();
+	if busy.is_empty() {
+		return check("busy_processes", Status::Pass, "none");
+	}
+	busy.sort_by(|a, b| b.0.total_cmp(&a.0));
+	let names: Vec<String> = busy.into_iter().map(|(_, s)| s).collect();
+	check("busy_processes", Status::Warn, names.join(", "))
+}
+
+pub fn run() -> Vec<Check> {
+	vec![check_governor(), check_turbo(), check_power(), check_aslr(), check_load(), check_busy_processes()]
+}
+
+// Prints every warning; returns how many there were.
+pub fn report(checks: &[Check]) -> usize {
+	let mut warnings = 0;
+	for c in checks.iter().filter(|c| c.status == Status::Warn) {
+		eprintln!("warning: preflight {}: {}", c.name, c.detail);
+		warnings += 1;
+	}
+	warnings
+}
+
+pub fn to_json(checks: &[Check]) -> Vec<JsonObject> {
+	checks
+		.iter()
+		.map(|c| {
+			let mut o = JsonObject::default();
+			o.str("check", c.name).str("status", c.status.name()).str("detail", &c.detail);
+			o
+		})
+		.collect()
+}
@@ -0,0 +1,124 @@
+use super::Sorter;
+use crate::elem::IntElem;
+
+pub struct BuiltinUnstable;
+
+impl<T: IntElem> Sorter<T> for BuiltinUnstable {
+	fn name(&self) -> &'static str {
+		"builtin_unstable"
+	}
+
+	fn description(&self) -> &'static str {
+		"slice::sort_unstable (alias: builtin)"
+	}
+
+	fn is_comparison(&self) -> bool {
+		true
+	}
+
+	fn counts_moves(&self) -> bool {
+		false
+	}
+
+	fn sort(&mut self, v: &mut [T]) {
+		v.sort_unstable();
+	}
+}
+
+pub struct BuiltinStable;
+
+impl<T: IntElem> Sorter<T> for BuiltinStable {
+	fn name(&self) -> &'static str {
+		"builtin_stable"
+	}
+
+	fn description(&self) -> &'static str {
+		"slice::sort"
+	}
+
+	fn is_comparison(&self) -> bool {
+		true
+	}
+
+	fn counts_moves(&self) -> bool {
+		false
+	}
+
+	fn sort(&mut self, v: &mut [T]) {
+		v.sort();
+	}
+}
+
+pub struct BuiltinUnstableBy;
+
+impl<T: IntElem> Sorter<T> for BuiltinUnstableBy {
+	fn name(&self) -> &'static str {
+		"builtin_unstable_by"
+	}
+
+	fn description(&self) -> &'static str {
+		"slice::sort_unstable_by with a closure comparator"
+	}
+
+	fn is_comparison(&self) -> bool {
+		true
+	}
+
+	fn counts_moves(&self) -> bool {
+		false
+	}
+
+	// The closure is what's being measured, so don't let clippy fold it away.
+	#[allow(clippy::unnecessary_sort_by)]
+	fn sort(&mut self, v: &mut [T]) {
+		v.sort_unstable_by(|a, b| a.cmp(b));
+	}
+}
+
+pub struct BuiltinByKey;
+
+impl<T: IntElem> Sorter<T> for BuiltinByKey {
+	fn name(&self) -> &'static str {
+		"builtin_by_key"
+	}
+
+	fn description(&self) -> &'static str {
+		"slice::sort_by_key (stable), identity key"
+	}
+
+	fn is_comparison(&self) -> bool {
+		true
+	}
+
+	fn counts_moves(&self) -> bool {
+		false
+	}
+
+	fn sort(&mut self, v: &mut [T]) {
+		v.sort_by_key(|&x| x);
+	}
+}
+
+pub struct BuiltinByCachedKey;
+
+impl<T: IntElem> Sorter<T> for BuiltinByCachedKey {
+	fn name(&self) -> &'static str {
+		"builtin_by_cached_key"
+	}
+
+	fn description(&self) -> &'static str {
+		"slice::sort_by_cached_key (stable), identity key; allocates a key/index table"
+	}
+
+	fn is_comparison(&self) -> bool {
+		true
+	}
+
+	fn counts_moves(&self) -> bool {
+		false
+	}
+
+	fn sort(&mut self, v: &mut [T]) {
+		v.sort_by_cached_key(|&x| x);
+	}
+}
@@ -0,0 +1,85 @@
+use super::radix::radix_lsd_by_key;
+use super::Sorter;
+use crate::elem::FloatElem;
+
+// What a float sort guarantees about NaNs, and so what validation checks.
+#[derive(Clone, Copy, PartialEq)]
+pub enum NanPolicy {
+	// the whole slice is ordered by total_cmp (NaNs at the ends by sign)
+	TotalOrder,
+	// NaNs are moved to the tail in any order; the rest is ordered by partial_cmp
+	FilteredToTail,
+}
+
+#[derive(Clone, Copy)]
+pub enum FloatMode {
+	TotalCmp,
+	PartialCmpFilterNan,
+	RadixLsd,
+}
+
+pub const FLOAT_ALGOS: &[&str] = &["total_cmp", "partial_cmp_filter_nan", "radix_lsd"];
+
+pub struct FloatSort {
+	pub mode: FloatMode,
+}
+
+impl FloatSort {
+	pub fn find(name: &str) -> Option<FloatSort> {
+		let mode = match name {
+			"builtin" | "total_cmp" => FloatMode::TotalCmp,
+			"partial_cmp_filter_nan" => FloatMode::PartialCmpFilterNan,
+			"radix_lsd" => FloatMode::RadixLsd,
+			_ => return None,
+		};
+		Some(FloatSort { mode })
+	}
+
+	pub fn nan_policy(&self) -> NanPolicy {
+		match self.mode {
+			FloatMode::TotalCmp | FloatMode::RadixLsd => NanPolicy::TotalOrder,
+			FloatMode::PartialCmpFilterNan => NanPolicy::FilteredToTail,
+		}
+	}
+}
+
+impl<T: FloatElem> Sorter<T> for FloatSort {
+	fn name(&self) -> &'static str {
+		match self.mode {
+			FloatMode::TotalCmp => "total_cmp",
+			FloatMode::PartialCmpFilterNan => "partial_cmp_filter_nan",
+			FloatMode::RadixLsd => "radix_lsd",
+		}
+	}
+
+	fn description(&self) -> &'static str {
+		match self.mode {
+			FloatMode::TotalCmp => "slice::sort_unstable_by(total_cmp) (alias: builtin)",
+			FloatMode::PartialCmpFilterNan => "NaNs partitioned to the tail, rest sort_unstable_by(partial_cmp)",
+			FloatMode::RadixLsd => "LSD radix sort on the total-order bit pattern",
+		}
+	}
+
+	fn is_comparison(&self) -> bool {
+		!matches!(self.mode, FloatMode::RadixLsd)
+	}
+
+	fn sort(&mut self, v: &mut [T]) {
+		match self.mode {
+			FloatMode::TotalCmp => v.sort_unstable_by(T::total_cmp),
+			FloatMode::PartialCmpFilterNan => {
+				// In-place partition instead of a filtering copy, so the slice
+				// keeps its length and the NaN count can be checked afterwards.
+				let mut keep = 0;
+				for i in 0..v.len() {
+					if !v[i].is_nan() {
+						v.swap(i, keep);
+						keep += 1;
+					}
+				}
+				v[..keep].sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+			}
+			FloatMode::RadixLsd => radix_lsd_by_key(v, T::BYTES, T::radix_key),
+		}
+	}
+}
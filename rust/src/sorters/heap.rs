@@ -0,0 +1,51 @@
+use super::ops::{self, CountOps};
+use super::Sorter;
+use crate::elem::IntElem;
+
+fn sift_down<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], mut root: usize, is_less: &mut F) {
+	loop {
+		let mut child = 2 * root + 1;
+		if child >= v.len() {
+			return;
+		}
+		if child + 1 < v.len() && is_less(&v[child], &v[child + 1]) {
+			child += 1;
+		}
+		if !is_less(&v[root], &v[child]) {
+			return;
+		}
+		ops::swap(v, root, child);
+		root = child;
+	}
+}
+
+pub fn heap_sort_by<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], is_less: &mut F) {
+	let n = v.len();
+	for i in (0..n / 2).rev() {
+		sift_down(v, i, is_less);
+	}
+	for end in (1..n).rev() {
+		ops::swap(v, 0, end);
+		sift_down(&mut v[..end], 0, is_less);
+	}
+}
+
+pub struct Heap;
+
+impl<T: IntElem> Sorter<T> for Heap {
+	fn name(&self) -> &'static str {
+		"heap"
+	}
+
+	fn description(&self) -> &'static str {
+		"binary max-heap heapsort"
+	}
+
+	fn is_comparison(&self) -> bool {
+		true
+	}
+
+	fn sort(&mut self, v: &mut [T]) {
+		heap_sort_by(v, &mut |a, b| a < b);
+	}
+}
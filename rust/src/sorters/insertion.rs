@@ -0,0 +1,165 @@
+use super::ops::CountOps;
+use super::Sorter;
+use crate::elem::IntElem;
+
+pub fn insertion_sort_by<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], is_less: &mut F) {
+	for i in 1..v.len() {
+		let x = v[i].mv();
+		let mut j = i;
+		while j > 0 && is_less(&x, &v[j - 1]) {
+			v[j] = v[j - 1].mv();
+			j -= 1;
+		}
+		v[j] = x.mv();
+	}
+}
+
+pub struct Insertion;
+
+impl<T: IntElem> Sorter<T> for Insertion {
+	fn name(&self) -> &'static str {
+		"insertion"
+	}
+
+	fn description(&self) -> &'static str {
+		"insertion sort (quadratic)"
+	}
+
+	fn is_comparison(&self) -> bool {
+		true
+	}
+
+	fn is_quadratic(&self) -> bool {
+		true
+	}
+
+	fn sort(&mut self, v: &mut [T]) {
+		insertion_sort_by(v, &mut |a, b| a < b);
+	}
+}
+
+#[derive(Clone, Debug)]
+pub enum GapSequence {
+	// n/2, n/4, ..., 1 (Shell 1959); quadratic worst case
+	Shell,
+	// 1, 4, 13, 40, ... (Knuth, (3^k - 1) / 2)
+	Knuth,
+	// 1, 8, 23, 77, 281, ... (Sedgewick 1986, 4^k + 3*2^(k-1) + 1)
+	Sedgewick,
+	// 1, 4, 10, 23, 57, 132, 301, 701, then *2.25 (Ciura 2001)
+	Ciura,
+	Custom(Vec<usize>),
+}
+
+impl GapSequence {
+	pub fn parse(s: &str) -> Option<GapSequence> {
+		match s {
+			"shell" => Some(GapSequence::Shell),
+			"knuth" => Some(GapSequence::Knuth),
+			"sedgewick" => Some(GapSequence::Sedgewick),
+			"ciura" => Some(GapSequence::Ciura),
+			_ => {
+				let mut gaps = Vec::new();
+				for part in s.split(',') {
+					gaps.push(part.trim().parse::<usize>().ok().filter(|&g| g > 0)?);
+				}
+				Some(GapSequence::Custom(gaps))
+			}
+		}
+	}
+
+	// Gaps smaller than n, largest first, always ending in 1.
+	fn gaps(&self, n: usize) -> Vec<usize> {
+		let mut gaps: Vec<usize> = match self {
+			GapSequence::Shell => {
+				let mut g = Vec::new();
+				let mut h = n / 2;
+				while h > 0 {
+					g.push(h);
+					h /= 2;
+				}
+				g
+			}
+			GapSequence::Knuth => {
+				let mut g = Vec::new();
+				let mut h = 1;
+				while h < n {
+					g.push(h);
+					h = 3 * h + 1;
+				}
+				g
+			}
+			GapSequence::Sedgewick => {
+				let mut g = vec![1];
+				let mut k = 1;
+				loop {
+					let h = 4usize.pow(k) + 3 * 2usize.pow(k - 1) + 1;
+					if h >= n {
+						break;
+					}
+					g.push(h);
+					k += 1;
+				}
+				g
+			}
+			GapSequence::Ciura => {
+				let mut g: Vec<usize> = vec![1, 4, 10, 23, 57, 132, 301, 701];
+				let mut h = 701.0f64;
+				while (h as usize) < n {
+					h *= 2.25;
+					g.push(h as usize);
+				}
+				g.retain(|&h| h < n);
+				g
+			}
+			GapSequence::Custom(g) => g.iter().copied().filter(|&h| h < n).collect(),
+		};
+		gaps.sort_unstable_by(|a, b| b.cmp(a));
+		gaps.dedup();
+		if gaps.last() != Some(&1) {
+			gaps.push(1);
+		}
+		gaps
+	}
+}
+
+pub fn shell_sort_by<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], gaps: &GapSequence, is_less: &mut F) {
+	for gap in gaps.gaps(v.len()) {
+		for i in gap..v.len() {
+			let x = v[i].mv();
+			let mut j = i;
+			while j >= gap && is_less(&x, &v[j - gap]) {
+				v[j] = v[j - gap].mv();
+				j -= gap;
+			}
+			v[j] = x.mv();
+		}
+	}
+}
+
+pub struct Shell {
+	pub gaps: GapSequence,
+}
+
+impl<T: IntElem> Sorter<T> for Shell {
+	fn name(&self) -> &'static str {
+		"shell"
+	}
+
+	fn description(&self) -> &'static str {
+		"shell sort, gap sequence from --shell-gaps (default ciura)"
+	}
+
+	fn is_comparison(&self) -> bool {
+		true
+	}
+
+	// Only Shell's original halving sequence (or a hand-picked list) can go quadratic.
+	fn is_quadratic(&self) -> bool {
+		matches!(self.gaps, GapSequence::Shell | GapSequence::Custom(_))
+	}
+
+	fn sort(&mut self, v: &mut [T]) {
+		shell_sort_by(v, &self.gaps, &mut |a, b| a < b);
+	}
+}
@@ -0,0 +1,125 @@
+use super::ops::{self, CountOps};
+use super::Sorter;
+use crate::elem::IntElem;
+
+// Merges the sorted runs a and b into out (out.len() == a.len() + b.len()).
+// Takes from a on ties, which keeps the merge stable.
+pub fn merge_into<T: CountOps, F: FnMut(&T, &T) -> bool>(a: &[T], b: &[T], out: &mut [T], is_less: &mut F) {
+	let (mut i, mut j, mut k) = (0, 0, 0);
+	while i < a.len() && j < b.len() {
+		if is_less(&b[j], &a[i]) {
+			out[k] = b[j].mv();
+			j += 1;
+		} else {
+			out[k] = a[i].mv();
+			i += 1;
+		}
+		k += 1;
+	}
+	ops::copy(&mut out[k..k + a.len() - i], &a[i..]);
+	k += a.len() - i;
+	ops::copy(&mut out[k..], &b[j..]);
+}
+
+fn top_down<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], buf: &mut [T], is_less: &mut F) {
+	let n = v.len();
+	if n < 2 {
+		return;
+	}
+	let mid = n / 2;
+	top_down(&mut v[..mid], buf, is_less);
+	top_down(&mut v[mid..], buf, is_less);
+	if !is_less(&v[mid], &v[mid - 1]) {
+		return;
+	}
+	ops::copy(&mut buf[..mid], &v[..mid]);
+	let (left, _) = buf.split_at(mid);
+	// The right half is read from v while the output overwrites v from the
+	// front; the write index never overtakes the unread part of the right half.
+	let (mut i, mut j, mut k) = (0, mid, 0);
+	while i < mid && j < n {
+		if is_less(&v[j], &left[i]) {
+			v[k] = v[j].mv();
+			j += 1;
+		} else {
+			v[k] = left[i].mv();
+			i += 1;
+		}
+		k += 1;
+	}
+	ops::copy(&mut v[k..k + mid - i], &left[i..]);
+}
+
+pub fn merge_sort_top_down_by<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], is_less: &mut F) {
+	if v.len() < 2 {
+		return;
+	}
+	// Only the left half is ever copied out, so n/2 scratch is enough.
+	let mut buf = ops::to_vec(&v[..v.len() / 2]);
+	top_down(v, &mut buf, is_less);
+}
+
+pub fn merge_sort_bottom_up_by<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], is_less: &mut F) {
+	let n = v.len();
+	if n < 2 {
+		return;
+	}
+	let mut buf = ops::to_vec(v);
+	let mut in_buf = false;
+	let mut width = 1;
+	while width < n {
+		let (src, dst): (&[T], &mut [T]) = if in_buf { (&buf, v) } else { (v, &mut buf) };
+		let mut lo = 0;
+		while lo < n {
+			let mid = (lo + width).min(n);
+			let hi = (lo + 2 * width).min(n);
+			merge_into(&src[lo..mid], &src[mid..hi], &mut dst[lo..hi], is_less);
+			lo = hi;
+		}
+		in_buf = !in_buf;
+		width *= 2;
+	}
+	if in_buf {
+		ops::copy(v, &buf);
+	}
+}
+
+pub struct MergeTopDown;
+
+impl<T: IntElem> Sorter<T> for MergeTopDown {
+	fn name(&self) -> &'static str {
+		"merge_top_down"
+	}
+
+	fn description(&self) -> &'static str {
+		"recursive top-down merge sort, n/2 scratch"
+	}
+
+	fn is_comparison(&self) -> bool {
+		true
+	}
+
+	fn sort(&mut self, v: &mut [T]) {
+		merge_sort_top_down_by(v, &mut |a, b| a < b);
+	}
+}
+
+pub struct MergeBottomUp;
+
+impl<T: IntElem> Sorter<T> for MergeBottomUp {
+	fn name(&self) -> &'static str {
+		"merge_bottom_up"
+	}
+
+	fn description(&self) -> &'static str {
+		"iterative bottom-up merge sort, ping-pong n-sized scratch"
+	}
+
+	fn is_comparison(&self) -> bool {
+		true
+	}
+
+	fn sort(&mut self, v: &mut [T]) {
+		merge_sort_bottom_up_by(v, &mut |a, b| a < b);
+	}
+}
@@ -0,0 +1,232 @@
+mod builtin;
+mod float;
+mod heap;
+mod insertion;
+mod merge;
+mod ops;
+mod parallel;
+mod powersort;
+mod quick;
+mod radix;
+mod string;
+mod timsort;
+
+use crate::elem::IntElem;
+pub use float::{FloatSort, NanPolicy, FLOAT_ALGOS};
+pub use insertion::GapSequence;
+pub use ops::{read_counts, reset_counts, CountOps, Counted, OpCounts};
+pub use string::{StrSort, STR_ALGOS};
+
+// A sort implementation selectable through --algo, for any dataset element type.
+// The timing loop only ever talks to this trait, so new algorithms plug in
+// by adding an entry to `registry()`.
+pub trait Sorter<T> {
+	fn name(&self) -> &'static str;
+	fn description(&self) -> &'static str;
+	fn sort(&mut self, v: &mut [T]);
+
+	// Quadratic algorithms are refused above --max-quadratic-n.
+	fn is_quadratic(&self) -> bool {
+		false
+	}
+
+	// Parallel algorithms honour --threads; everything else runs on one thread.
+	fn is_parallel(&self) -> bool {
+		false
+	}
+
+	// Whether the algorithm only looks at elements through Ord (radix sorts
+	// read the keys directly); comparison-driven tooling such as antiqsort needs
+	// it. Off unless a sorter opts in, since antiqsort's elements have no keys.
+	fn is_comparison(&self) -> bool {
+		false
+	}
+
+	// Whether --count-ops sees the swaps and moves too: false for sorts that
+	// hand the work to std, where only comparisons are observable.
+	fn counts_moves(&self) -> bool {
+		true
+	}
+
+	// Run statistics from the most recent sort() call, for run-adaptive algorithms.
+	fn run_stats(&self) -> Option<RunStats> {
+		None
+	}
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunStats {
+	// runs pushed on the merge stack (natural runs, after minrun extension)
+	pub runs: usize,
+	pub max_stack_depth: usize,
+}
+
+// Knobs for algorithms that have them; filled from the command line.
+#[derive(Clone, Debug)]
+pub struct SortConfig {
+	pub shell_gaps: GapSequence,
+	pub threads: usize,
+}
+
+impl Default for SortConfig {
+	fn default() -> Self {
+		SortConfig { shell_gaps: GapSequence::Ciura, threads: 1 }
+	}
+}
+
+pub fn registry<T: IntElem>(cfg: &SortConfig) -> Vec<Box<dyn Sorter<T>>> {
+	vec![
+		Box::new(builtin::BuiltinUnstable),
+		Box::new(builtin::BuiltinStable),
+		Box::new(builtin::BuiltinUnstableBy),
+		Box::new(builtin::BuiltinByKey),
+		Box::new(builtin::BuiltinByCachedKey),
+		Box::new(radix::RadixLsd),
+		Box::new(radix::RadixMsd),
+		Box::new(merge::MergeTopDown),
+		Box::new(merge::MergeBottomUp),
+		Box::new(heap::Heap),
+		Box::new(timsort::Timsort::default()),
+		Box::new(powersort::Powersort::default()),
+		Box::new(quick::Quick { scheme: quick::Partition::Lomuto }),
+		Box::new(quick::Quick { scheme: quick::Partition::Hoare }),
+		Box::new(insertion::Shell { gaps: cfg.shell_gaps.clone() }),
+		Box::new(insertion::Insertion),
+		Box::new(parallel::ParallelMerge { threads: cfg.threads }),
+		Box::new(parallel::ParallelSample { threads: cfg.threads }),
+	]
+}
+
+// "builtin" is what every language in the harness accepts (see scripts/run_all.sh).
+fn canonical_name(name: &str) -> &str {
+	match name {
+		"builtin" => "builtin_unstable",
+		other => other,
+	}
+}
+
+pub fn find<T: IntElem>(name: &str, cfg: &SortConfig) -> Option<Box<dyn Sorter<T>>> {
+	let name = canonical_name(name);
+	registry(cfg).into_iter().find(|s| s.name() == name)
+}
+
+pub fn print_list() {
+	println!("integer datasets (i32, i64, u64):");
+	for s in registry::<i32>(&SortConfig::default()) {
+		let note = if s.is_quadratic() {
+			" [quadratic]"
+		} else if s.is_parallel() {
+			" [--threads]"
+		} else {
+			""
+		};
+		println!("{:<20} {}{}", s.name(), s.description(), note);
+	}
+	println!();
+	println!("float datasets (f32, f64):");
+	for name in FLOAT_ALGOS {
+		let s = FloatSort::find(name).unwrap();
+		println!("{:<20} {}", name, Sorter::<f64>::description(&s));
+	}
+	println!();
+	println!("string datasets (str):");
+	for name in STR_ALGOS {
+		println!("{:<20} {}", name, StrSort::find(name).unwrap().description());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	struct XorShift(u64);
+
+	impl XorShift {
+		fn next(&mut self) -> u64 {
+			self.0 ^= self.0 << 13;
+			self.0 ^= self.0 >> 7;
+			self.0 ^= self.0 << 17;
+			self.0
+		}
+	}
+
+	// Around MIN_RUN (powersort), min_run_length (timsort) and STR_SMALL, plus
+	// sizes above the parallel sorts' 1 << 14 cutoff.
+	const LENGTHS: &[usize] = &[0, 1, 2, 3, 15, 16, 17, 31, 32, 33, 63, 64, 65, 100, 1000, 5000, 40_000];
+
+	// (name, input) for every shape we sort; `draw` maps a random u64 to an element.
+	fn inputs<T: IntElem>(n: usize, draw: fn(u64) -> T) -> Vec<(&'static str, Vec<T>)> {
+		let mut rng = XorShift(0x9e37_79b9_7f4a_7c15 ^ n as u64);
+		let random: Vec<T> = (0..n).map(|_| draw(rng.next())).collect();
+		let dups: Vec<T> = (0..n).map(|_| draw(rng.next() % 8)).collect();
+		let mut sorted = random.clone();
+		sorted.sort_unstable();
+		let reversed: Vec<T> = sorted.iter().rev().copied().collect();
+		let mut nearly = sorted.clone();
+		for i in (0..n).step_by(7) {
+			nearly[i] = draw(rng.next());
+		}
+		let equal = vec![draw(42); n];
+		vec![("random", random), ("dups", dups), ("sorted", sorted), ("reversed", reversed), ("nearly_sorted", nearly), ("all_equal", equal)]
+	}
+
+	fn check_registry<T: IntElem>(draw: fn(u64) -> T) {
+		let cfg = SortConfig { threads: 4, ..SortConfig::default() };
+		for &n in LENGTHS {
+			for (shape, input) in inputs(n, draw) {
+				let mut expected = input.clone();
+				expected.sort_unstable();
+				for mut sorter in registry::<T>(&cfg) {
+					if sorter.is_quadratic() && n > 5000 {
+						continue;
+					}
+					let mut v = input.clone();
+					sorter.sort(&mut v);
+					assert!(v == expected, "{} {} n={} ({})", sorter.name(), shape, n, T::NAME);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn registry_sorts_i32() {
+		check_registry::<i32>(|x| x as i32);
+	}
+
+	#[test]
+	fn registry_sorts_i64() {
+		check_registry::<i64>(|x| x as i64);
+	}
+
+	#[test]
+	fn registry_sorts_u64() {
+		check_registry::<u64>(|x| x);
+	}
+
+	// Key plus original position; only the key is compared.
+	impl CountOps for (u8, u32) {}
+
+	type StableSort = fn(&mut [(u8, u32)]);
+
+	#[test]
+	fn stable_kernels_keep_equal_keys_in_order() {
+		let stable: &[(&str, StableSort)] = &[
+			("timsort", |v| timsort::timsort_by(v, &mut RunStats::default(), &mut |a, b| a.0 < b.0)),
+			("powersort", |v| powersort::powersort_by(v, &mut RunStats::default(), &mut |a, b| a.0 < b.0)),
+			("merge_top_down", |v| merge::merge_sort_top_down_by(v, &mut |a, b| a.0 < b.0)),
+			("merge_bottom_up", |v| merge::merge_sort_bottom_up_by(v, &mut |a, b| a.0 < b.0)),
+		];
+		for &n in LENGTHS {
+			for (shape, input) in inputs::<i32>(n, |x| x as i32) {
+				let pairs: Vec<(u8, u32)> = input.iter().enumerate().map(|(i, &x)| (x as u8 % 4, i as u32)).collect();
+				let mut expected = pairs.clone();
+				expected.sort_by_key(|p| p.0);
+				for (name, sort) in stable {
+					let mut v = pairs.clone();
+					sort(&mut v);
+					assert!(v == expected, "{} {} n={}", name, shape, n);
+				}
+			}
+		}
+	}
+}
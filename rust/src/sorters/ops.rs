@@ -0,0 +1,145 @@
+use std::cmp::Ordering;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+
+use crate::elem::{Elem, IntElem};
+
+// Hooks our own kernels call on every element swap and copy, for --count-ops.
+// They are no-ops that compile away for every element type except Counted.
+pub trait CountOps: Copy {
+	// Wraps one element assignment: `v[j] = v[i].mv()`.
+	#[inline(always)]
+	fn mv(self) -> Self {
+		self
+	}
+
+	#[inline(always)]
+	fn on_moves(_n: usize) {}
+
+	#[inline(always)]
+	fn on_swaps(_n: usize) {}
+}
+
+impl CountOps for i32 {}
+impl CountOps for i64 {}
+impl CountOps for u64 {}
+impl CountOps for f32 {}
+impl CountOps for f64 {}
+impl CountOps for &str {}
+
+#[inline]
+pub fn swap<T: CountOps>(v: &mut [T], a: usize, b: usize) {
+	T::on_swaps(1);
+	v.swap(a, b);
+}
+
+#[inline]
+pub fn reverse<T: CountOps>(v: &mut [T]) {
+	T::on_swaps(v.len() / 2);
+	v.reverse();
+}
+
+#[inline]
+pub fn copy<T: CountOps>(dst: &mut [T], src: &[T]) {
+	T::on_moves(src.len());
+	dst.copy_from_slice(src);
+}
+
+#[inline]
+pub fn copy_within<T: CountOps>(v: &mut [T], src: std::ops::Range<usize>, dest: usize) {
+	T::on_moves(src.len());
+	v.copy_within(src, dest);
+}
+
+#[inline]
+pub fn to_vec<T: CountOps>(v: &[T]) -> Vec<T> {
+	T::on_moves(v.len());
+	v.to_vec()
+}
+
+// Process-wide, since parallel sorts compare on several threads.
+static COMPARISONS: AtomicU64 = AtomicU64::new(0);
+static SWAPS: AtomicU64 = AtomicU64::new(0);
+static MOVES: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Clone, Copy, Debug)]
+pub struct OpCounts {
+	pub comparisons: u64,
+	// None for sorts whose swaps and moves happen inside std
+	pub swaps: Option<u64>,
+	pub moves: Option<u64>,
+}
+
+pub fn reset_counts() {
+	COMPARISONS.store(0, AtomicOrdering::Relaxed);
+	SWAPS.store(0, AtomicOrdering::Relaxed);
+	MOVES.store(0, AtomicOrdering::Relaxed);
+}
+
+pub fn read_counts(counts_moves: bool) -> OpCounts {
+	let moves_or_none = |c: &AtomicU64| counts_moves.then(|| c.load(AtomicOrdering::Relaxed));
+	OpCounts {
+		comparisons: COMPARISONS.load(AtomicOrdering::Relaxed),
+		swaps: moves_or_none(&SWAPS),
+		moves: moves_or_none(&MOVES),
+	}
+}
+
+// An element that tallies every comparison made through Ord and every swap
+// and move made through the CountOps hooks.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Counted<T>(pub T);
+
+impl<T: Ord> Ord for Counted<T> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		COMPARISONS.fetch_add(1, AtomicOrdering::Relaxed);
+		self.0.cmp(&other.0)
+	}
+}
+
+impl<T: Ord> PartialOrd for Counted<T> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<T: Ord> PartialEq for Counted<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl<T: Ord> Eq for Counted<T> {}
+
+impl<T: IntElem> Elem for Counted<T> {
+	const NAME: &'static str = T::NAME;
+	const BYTES: usize = T::BYTES;
+
+	fn from_le(bytes: &[u8]) -> Self {
+		Counted(T::from_le(bytes))
+	}
+}
+
+impl<T: IntElem> IntElem for Counted<T> {
+	#[inline]
+	fn radix_key(self) -> u64 {
+		self.0.radix_key()
+	}
+}
+
+impl<T: Copy> CountOps for Counted<T> {
+	#[inline]
+	fn mv(self) -> Self {
+		MOVES.fetch_add(1, AtomicOrdering::Relaxed);
+		self
+	}
+
+	#[inline]
+	fn on_moves(n: usize) {
+		MOVES.fetch_add(n as u64, AtomicOrdering::Relaxed);
+	}
+
+	#[inline]
+	fn on_swaps(n: usize) {
+		SWAPS.fetch_add(n as u64, AtomicOrdering::Relaxed);
+	}
+}
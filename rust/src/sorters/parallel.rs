@@ -0,0 +1,177 @@
+use std::thread;
+
+use super::merge::merge_into;
+use super::ops::CountOps;
+use super::Sorter;
+use crate::elem::IntElem;
+
+// Below this many elements a subproblem is not worth a thread.
+const PAR_CUTOFF: usize = 1 << 14;
+
+// Merges sorted a and b into out using up to `threads` threads: split the
+// longer input at its midpoint, binary-search the split point in the other
+// one, and merge the two halves independently.
+fn par_merge<T: Ord + CountOps + Send + Sync>(a: &[T], b: &[T], out: &mut [T], threads: usize) {
+	if threads <= 1 || out.len() < PAR_CUTOFF {
+		merge_into(a, b, out, &mut |x, y| x < y);
+		return;
+	}
+	// Ties must stay on the a side to keep the merge stable.
+	let (ia, ib) = if a.len() >= b.len() {
+		let ia = a.len() / 2;
+		(ia, b.partition_point(|x| *x < a[ia]))
+	} else {
+		let ib = b.len() / 2;
+		(a.partition_point(|x| *x <= b[ib]), ib)
+	};
+	let (a_lo, a_hi) = a.split_at(ia);
+	let (b_lo, b_hi) = b.split_at(ib);
+	let (out_lo, out_hi) = out.split_at_mut(ia + ib);
+	let left_threads = threads / 2;
+	thread::scope(|s| {
+		s.spawn(|| par_merge(a_lo, b_lo, out_lo, left_threads));
+		par_merge(a_hi, b_hi, out_hi, threads - left_threads);
+	});
+}
+
+fn par_merge_sort<T: Ord + CountOps + Send + Sync>(v: &mut [T], buf: &mut [T], threads: usize) {
+	if threads <= 1 || v.len() < PAR_CUTOFF {
+		v.sort();
+		return;
+	}
+	let mid = v.len() / 2;
+	let left_threads = threads / 2;
+	{
+		let (v_lo, v_hi) = v.split_at_mut(mid);
+		let (b_lo, b_hi) = buf.split_at_mut(mid);
+		// Each half copies itself into the scratch buffer on its own thread,
+		// so the merge below reads from buf and writes straight into v.
+		thread::scope(|s| {
+			s.spawn(|| {
+				par_merge_sort(v_lo, b_lo, left_threads);
+				b_lo.copy_from_slice(v_lo);
+			});
+			par_merge_sort(v_hi, b_hi, threads - left_threads);
+			b_hi.copy_from_slice(v_hi);
+		});
+	}
+	let (b_lo, b_hi) = buf.split_at(mid);
+	par_merge(b_lo, b_hi, v, threads);
+}
+
+pub struct ParallelMerge {
+	pub threads: usize,
+}
+
+impl<T: IntElem> Sorter<T> for ParallelMerge {
+	fn name(&self) -> &'static str {
+		"par_merge"
+	}
+
+	fn description(&self) -> &'static str {
+		"parallel merge sort (thread::scope), slice::sort leaves, parallel merges"
+	}
+
+	fn is_comparison(&self) -> bool {
+		true
+	}
+
+	fn is_parallel(&self) -> bool {
+		true
+	}
+
+	fn counts_moves(&self) -> bool {
+		false
+	}
+
+	fn sort(&mut self, v: &mut [T]) {
+		let mut buf = vec![T::default(); v.len()];
+		par_merge_sort(v, &mut buf, self.threads);
+	}
+}
+
+// Candidates drawn per bucket when choosing splitters.
+const OVERSAMPLE: usize = 32;
+
+fn sample_sort<T: Ord + Copy + Send + Sync>(v: &mut [T], threads: usize) {
+	let n = v.len();
+	if threads <= 1 || n < PAR_CUTOFF {
+		v.sort_unstable();
+		return;
+	}
+
+	// Evenly strided sample; deterministic so reps stay comparable.
+	let buckets = threads;
+	let mut sample: Vec<T> = (0..buckets * OVERSAMPLE).map(|i| v[i * n / (buckets * OVERSAMPLE)]).collect();
+	sample.sort_unstable();
+	let splitters: Vec<T> = (1..buckets).map(|i| sample[i * OVERSAMPLE]).collect();
+	let splitters = &splitters;
+
+	// Classify: every thread splits its own chunk into per-bucket vectors.
+	let chunk = n.div_ceil(threads);
+	let local: Vec<Vec<Vec<T>>> = thread::scope(|s| {
+		let handles: Vec<_> = v
+			.chunks(chunk)
+			.map(|part| {
+				s.spawn(move || {
+					let mut out: Vec<Vec<T>> = (0..buckets).map(|_| Vec::with_capacity(part.len() / buckets)).collect();
+					for &x in part {
+						out[splitters.partition_point(|s| *s <= x)].push(x);
+					}
+					out
+				})
+			})
+			.collect();
+		handles.into_iter().map(|h| h.join().unwrap()).collect()
+	});
+
+	// Distribute: bucket b owns a contiguous region of v; one thread gathers
+	// it from all chunks and sorts it.
+	let local = &local;
+	thread::scope(|s| {
+		let mut rest: &mut [T] = v;
+		for b in 0..buckets {
+			let size: usize = local.iter().map(|l| l[b].len()).sum();
+			let (region, tail) = rest.split_at_mut(size);
+			rest = tail;
+			s.spawn(move || {
+				let mut off = 0;
+				for l in local {
+					region[off..off + l[b].len()].copy_from_slice(&l[b]);
+					off += l[b].len();
+				}
+				region.sort_unstable();
+			});
+		}
+	});
+}
+
+pub struct ParallelSample {
+	pub threads: usize,
+}
+
+impl<T: IntElem> Sorter<T> for ParallelSample {
+	fn name(&self) -> &'static str {
+		"par_sample"
+	}
+
+	fn description(&self) -> &'static str {
+		"parallel sample sort (thread::scope), one bucket per thread"
+	}
+
+	fn is_comparison(&self) -> bool {
+		true
+	}
+
+	fn is_parallel(&self) -> bool {
+		true
+	}
+
+	fn counts_moves(&self) -> bool {
+		false
+	}
+
+	fn sort(&mut self, v: &mut [T]) {
+		sample_sort(v, self.threads);
+	}
+}
@@ -0,0 +1,103 @@
+use super::timsort::{binary_insertion_sort, count_run_and_make_ascending, merge_runs, Run};
+use super::ops::CountOps;
+use super::{RunStats, Sorter};
+use crate::elem::IntElem;
+
+// Short natural runs are extended to this length with binary insertion sort.
+const MIN_RUN: usize = 32;
+
+// Power of the boundary between run1 = [s1, s1 + n1) and run2 = [s1 + n1, s1 + n1 + n2):
+// the depth in a perfectly balanced merge tree at which their midpoints
+// (scaled to [0, 1)) first fall into different halves. Munro & Wild 2018.
+fn node_power(s1: usize, n1: usize, n2: usize, n: usize) -> u32 {
+	let mut a = 2 * s1 + n1;
+	let mut b = a + n1 + n2;
+	let mut power = 0;
+	loop {
+		power += 1;
+		if a >= n {
+			a -= n;
+			b -= n;
+		} else if b >= n {
+			break;
+		}
+		a <<= 1;
+		b <<= 1;
+	}
+	power
+}
+
+fn next_run<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], start: usize, is_less: &mut F) -> Run {
+	let n = v.len();
+	let mut len = count_run_and_make_ascending(&mut v[start..], is_less);
+	if len < MIN_RUN {
+		let force = MIN_RUN.min(n - start);
+		binary_insertion_sort(&mut v[start..start + force], len, is_less);
+		len = force;
+	}
+	Run { start, len }
+}
+
+// Merges are plain (non-galloping) merges of adjacent runs; only the merge
+// order differs from Timsort.
+pub fn powersort_by<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], stats: &mut RunStats, is_less: &mut F) {
+	*stats = RunStats::default();
+	let n = v.len();
+	if n < 2 {
+		return;
+	}
+
+	let mut buf: Vec<T> = Vec::with_capacity(n / 2);
+	let mut no_gallop = usize::MAX;
+	let mut stack: Vec<(Run, u32)> = Vec::new();
+
+	let mut a = next_run(v, 0, is_less);
+	stats.runs = 1;
+	while a.start + a.len < n {
+		let b = next_run(v, a.start + a.len, is_less);
+		stats.runs += 1;
+		let p = node_power(a.start, a.len, b.len, n);
+		while let Some(&(top, top_power)) = stack.last() {
+			if top_power <= p {
+				break;
+			}
+			stack.pop();
+			merge_runs(&mut v[top.start..a.start + a.len], top.len, &mut buf, &mut no_gallop, is_less);
+			a = Run { start: top.start, len: top.len + a.len };
+		}
+		stack.push((a, p));
+		stats.max_stack_depth = stats.max_stack_depth.max(stack.len());
+		a = b;
+	}
+	while let Some((top, _)) = stack.pop() {
+		merge_runs(&mut v[top.start..a.start + a.len], top.len, &mut buf, &mut no_gallop, is_less);
+		a = Run { start: top.start, len: top.len + a.len };
+	}
+}
+
+#[derive(Default)]
+pub struct Powersort {
+	last: RunStats,
+}
+
+impl<T: IntElem> Sorter<T> for Powersort {
+	fn name(&self) -> &'static str {
+		"powersort"
+	}
+
+	fn description(&self) -> &'static str {
+		"Powersort: natural runs merged by node power (std stable sort policy)"
+	}
+
+	fn is_comparison(&self) -> bool {
+		true
+	}
+
+	fn sort(&mut self, v: &mut [T]) {
+		powersort_by(v, &mut self.last, &mut |a, b| a < b);
+	}
+
+	fn run_stats(&self) -> Option<RunStats> {
+		Some(self.last)
+	}
+}
@@ -0,0 +1,133 @@
+use super::heap::heap_sort_by;
+use super::insertion::insertion_sort_by;
+use super::ops::{self, CountOps};
+use super::Sorter;
+use crate::elem::IntElem;
+
+const SMALL: usize = 16;
+
+#[derive(Clone, Copy)]
+pub enum Partition {
+	Lomuto,
+	Hoare,
+}
+
+// Moves the median of v[0], v[mid], v[last] to the last slot (Lomuto pivot position).
+fn median_of_three_to_end<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], is_less: &mut F) {
+	let last = v.len() - 1;
+	let mid = v.len() / 2;
+	if is_less(&v[mid], &v[0]) {
+		ops::swap(v, mid, 0);
+	}
+	if is_less(&v[last], &v[0]) {
+		ops::swap(v, last, 0);
+	}
+	if is_less(&v[mid], &v[last]) {
+		ops::swap(v, mid, last);
+	}
+}
+
+// Returns the final pivot index p: nothing in v[..p] is greater than v[p]
+// and nothing in v[p + 1..] is less.
+fn lomuto<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], is_less: &mut F) -> usize {
+	median_of_three_to_end(v, is_less);
+	let last = v.len() - 1;
+	let mut store = 0;
+	for i in 0..last {
+		if is_less(&v[i], &v[last]) {
+			ops::swap(v, i, store);
+			store += 1;
+		}
+	}
+	ops::swap(v, store, last);
+	store
+}
+
+// Same contract as lomuto, so both schemes share the recursion below.
+// Stops on keys equal to the pivot from both sides, which keeps dups balanced.
+fn hoare<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], is_less: &mut F) -> usize {
+	median_of_three_to_end(v, is_less);
+	let last = v.len() - 1;
+	ops::swap(v, 0, last);
+	let (mut i, mut j) = (1, last);
+	loop {
+		while i <= j && is_less(&v[i], &v[0]) {
+			i += 1;
+		}
+		while i <= j && is_less(&v[0], &v[j]) {
+			j -= 1;
+		}
+		if i >= j {
+			break;
+		}
+		ops::swap(v, i, j);
+		i += 1;
+		j -= 1;
+	}
+	ops::swap(v, 0, j);
+	j
+}
+
+// Introsort-style: once the recursion is deeper than 2*log2(n) the remaining
+// range is handed to heapsort, so adversarial inputs stay O(n log n).
+pub fn quick_sort_by<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], scheme: Partition, is_less: &mut F) {
+	let limit = 2 * (usize::BITS - v.len().leading_zeros());
+	quick_rec(v, scheme, limit, is_less);
+}
+
+fn quick_rec<T: CountOps, F: FnMut(&T, &T) -> bool>(mut v: &mut [T], scheme: Partition, mut limit: u32, is_less: &mut F) {
+	loop {
+		if v.len() <= SMALL {
+			insertion_sort_by(v, is_less);
+			return;
+		}
+		if limit == 0 {
+			heap_sort_by(v, is_less);
+			return;
+		}
+		limit -= 1;
+
+		let p = match scheme {
+			Partition::Lomuto => lomuto(v, is_less),
+			Partition::Hoare => hoare(v, is_less),
+		};
+		// Recurse into the smaller side and loop on the larger one to bound stack depth.
+		let (left, right) = v.split_at_mut(p);
+		let right = &mut right[1..];
+		if left.len() < right.len() {
+			quick_rec(left, scheme, limit, is_less);
+			v = right;
+		} else {
+			quick_rec(right, scheme, limit, is_less);
+			v = left;
+		}
+	}
+}
+
+pub struct Quick {
+	pub scheme: Partition,
+}
+
+impl<T: IntElem> Sorter<T> for Quick {
+	fn name(&self) -> &'static str {
+		match self.scheme {
+			Partition::Lomuto => "quick_lomuto",
+			Partition::Hoare => "quick_hoare",
+		}
+	}
+
+	fn description(&self) -> &'static str {
+		match self.scheme {
+			Partition::Lomuto => "quicksort, Lomuto partition, median-of-3, heapsort fallback",
+			Partition::Hoare => "quicksort, Hoare partition, median-of-3, heapsort fallback",
+		}
+	}
+
+	fn is_comparison(&self) -> bool {
+		true
+	}
+
+	fn sort(&mut self, v: &mut [T]) {
+		quick_sort_by(v, self.scheme, &mut |a, b| a < b);
+	}
+}
@@ -0,0 +1,155 @@
+use super::insertion::insertion_sort_by;
+use super::ops::{self, CountOps};
+use super::Sorter;
+use crate::elem::IntElem;
+
+// Byte `b` (0 = least significant) of the element's order-preserving key.
+#[inline]
+fn byte<T: IntElem>(x: T, b: usize) -> usize {
+	key_byte(x.radix_key(), b)
+}
+
+#[inline]
+fn key_byte(key: u64, b: usize) -> usize {
+	((key >> (b * 8)) & 0xff) as usize
+}
+
+pub struct RadixLsd;
+
+impl<T: IntElem> Sorter<T> for RadixLsd {
+	fn name(&self) -> &'static str {
+		"radix_lsd"
+	}
+
+	fn description(&self) -> &'static str {
+		"LSD radix sort, one pass per key byte, n-sized scratch buffer"
+	}
+
+	fn sort(&mut self, v: &mut [T]) {
+		radix_lsd_by_key(v, T::BYTES, T::radix_key);
+	}
+}
+
+// LSD radix sort on the low `bytes` bytes of key(x); key must be order-preserving.
+pub fn radix_lsd_by_key<T: CountOps + Default, K: Fn(T) -> u64>(v: &mut [T], bytes: usize, key: K) {
+	let n = v.len();
+	if n < 2 {
+		return;
+	}
+
+	// All per-byte histograms in a single pass over the input.
+	let mut counts = vec![[0usize; 256]; bytes];
+	for &x in v.iter() {
+		let k = key(x);
+		for (pass, c) in counts.iter_mut().enumerate() {
+			c[key_byte(k, pass)] += 1;
+		}
+	}
+
+	let mut scratch = vec![T::default(); n];
+	let mut in_scratch = false;
+
+	for (pass, c) in counts.iter().enumerate() {
+		// Every key has the same byte here; this pass would be a plain copy.
+		if c.contains(&n) {
+			continue;
+		}
+
+		let mut offsets = [0usize; 256];
+		let mut sum = 0;
+		for (o, &cnt) in offsets.iter_mut().zip(c.iter()) {
+			*o = sum;
+			sum += cnt;
+		}
+
+		let (src, dst): (&[T], &mut [T]) = if in_scratch {
+			(&scratch, v)
+		} else {
+			(v, &mut scratch)
+		};
+		for &x in src {
+			let b = key_byte(key(x), pass);
+			dst[offsets[b]] = x.mv();
+			offsets[b] += 1;
+		}
+		in_scratch = !in_scratch;
+	}
+
+	if in_scratch {
+		ops::copy(v, &scratch);
+	}
+}
+
+// Buckets at or below this size are finished with insertion sort.
+const MSD_SMALL_BUCKET: usize = 32;
+
+pub struct RadixMsd;
+
+impl<T: IntElem> Sorter<T> for RadixMsd {
+	fn name(&self) -> &'static str {
+		"radix_msd"
+	}
+
+	fn description(&self) -> &'static str {
+		"in-place MSD radix sort (American flag), insertion sort for small buckets"
+	}
+
+	fn sort(&mut self, v: &mut [T]) {
+		american_flag(v, T::BYTES - 1);
+	}
+}
+
+// Buckets by key byte `b`, then recurses into each bucket on byte b - 1.
+fn american_flag<T: IntElem>(v: &mut [T], b: usize) {
+	if v.len() <= MSD_SMALL_BUCKET {
+		insertion_sort_by(v, &mut |a, b| a < b);
+		return;
+	}
+
+	let digit = |x: T| byte(x, b);
+
+	let mut counts = [0usize; 256];
+	for &x in v.iter() {
+		counts[digit(x)] += 1;
+	}
+
+	let mut next = [0usize; 256];
+	let mut ends = [0usize; 256];
+	let mut sum = 0;
+	for d in 0..256 {
+		next[d] = sum;
+		sum += counts[d];
+		ends[d] = sum;
+	}
+
+	// Cycle-leader permutation: pick up the first misplaced element of a bucket
+	// and keep swapping it into its home bucket until something that belongs
+	// here comes back.
+	for home in 0..256 {
+		while next[home] < ends[home] {
+			let mut x = v[next[home]].mv();
+			loop {
+				let d = digit(x);
+				if d == home {
+					break;
+				}
+				T::on_swaps(1);
+				std::mem::swap(&mut x, &mut v[next[d]]);
+				next[d] += 1;
+			}
+			v[next[home]] = x.mv();
+			next[home] += 1;
+		}
+	}
+
+	if b == 0 {
+		return;
+	}
+	let mut start = 0;
+	for &end in ends.iter() {
+		if end - start > 1 {
+			american_flag(&mut v[start..end], b - 1);
+		}
+		start = end;
+	}
+}
@@ -0,0 +1,206 @@
+use super::insertion::insertion_sort_by;
+
+// Partitions at or below this size are finished with insertion sort on the
+// bytes from the current depth on.
+const STR_SMALL: usize = 16;
+
+#[derive(Clone, Copy)]
+pub enum StrSort {
+	// slice::sort_unstable on Vec<String>: moves 24-byte String headers
+	OwnedUnstable,
+	// slice::sort_unstable on Vec<&str> into the dataset: moves 16-byte
+	// slices, compares without touching the String headers
+	BorrowedUnstable,
+	MultikeyQuicksort,
+	MsdRadix,
+}
+
+pub const STR_ALGOS: &[&str] = &["owned_unstable", "borrowed_unstable", "multikey_quicksort", "msd_radix"];
+
+impl StrSort {
+	pub fn find(name: &str) -> Option<StrSort> {
+		match name {
+			"builtin" | "owned_unstable" => Some(StrSort::OwnedUnstable),
+			"borrowed_unstable" => Some(StrSort::BorrowedUnstable),
+			"multikey_quicksort" => Some(StrSort::MultikeyQuicksort),
+			"msd_radix" => Some(StrSort::MsdRadix),
+			_ => None,
+		}
+	}
+
+	pub fn description(self) -> &'static str {
+		match self {
+			StrSort::OwnedUnstable => "slice::sort_unstable on Vec<String> (alias: builtin)",
+			StrSort::BorrowedUnstable => "slice::sort_unstable on Vec<&str>",
+			StrSort::MultikeyQuicksort => "Bentley-Sedgewick 3-way radix quicksort on Vec<&str>",
+			StrSort::MsdRadix => "MSD radix sort by byte on Vec<&str>, n-sized scratch buffer",
+		}
+	}
+
+	// Whether the algorithm sorts owned Strings rather than &str.
+	pub fn is_owned(self) -> bool {
+		matches!(self, StrSort::OwnedUnstable)
+	}
+
+	pub fn sort_owned(self, v: &mut [String]) {
+		v.sort_unstable();
+	}
+
+	pub fn sort_borrowed(self, v: &mut [&str]) {
+		match self {
+			StrSort::OwnedUnstable | StrSort::BorrowedUnstable => v.sort_unstable(),
+			StrSort::MultikeyQuicksort => multikey_quicksort(v, 0),
+			StrSort::MsdRadix => {
+				let mut scratch = v.to_vec();
+				msd_radix(v, &mut scratch, 0);
+			}
+		}
+	}
+}
+
+// Byte d of s, or None past the end (which sorts before every byte).
+#[inline]
+fn byte_at(s: &str, d: usize) -> Option<u8> {
+	s.as_bytes().get(d).copied()
+}
+
+// Every string in v shares its first d bytes, so only the rest is compared.
+fn insertion_sort_from(v: &mut [&str], d: usize) {
+	insertion_sort_by(v, &mut |a, b| a.as_bytes()[d..] < b.as_bytes()[d..]);
+}
+
+// 3-way partition on byte d around a median-of-three pivot byte; the equal
+// part moves on to byte d + 1, the other two stay at d. The equal part is
+// handled by looping, so a long common prefix costs no stack.
+fn multikey_quicksort(mut v: &mut [&str], mut d: usize) {
+	loop {
+		let n = v.len();
+		if n <= STR_SMALL {
+			insertion_sort_from(v, d);
+			return;
+		}
+
+		let (a, b, c) = (byte_at(v[0], d), byte_at(v[n / 2], d), byte_at(v[n - 1], d));
+		let pivot = a.max(b).min(a.min(b).max(c));
+
+		let (mut lt, mut i, mut gt) = (0, 0, n);
+		while i < gt {
+			let x = byte_at(v[i], d);
+			if x < pivot {
+				v.swap(lt, i);
+				lt += 1;
+				i += 1;
+			} else if x > pivot {
+				gt -= 1;
+				v.swap(i, gt);
+			} else {
+				i += 1;
+			}
+		}
+
+		let (lo, rest) = std::mem::take(&mut v).split_at_mut(lt);
+		let (eq, hi) = rest.split_at_mut(gt - lt);
+		multikey_quicksort(lo, d);
+		multikey_quicksort(hi, d);
+		// Strings that ended at d are all equal.
+		if pivot.is_none() {
+			return;
+		}
+		v = eq;
+		d += 1;
+	}
+}
+
+// Bucket 0 holds strings that end before byte d, bucket 1 + b byte value b.
+fn msd_radix<'a>(v: &mut [&'a str], scratch: &mut [&'a str], mut d: usize) {
+	let n = v.len();
+	if n <= STR_SMALL {
+		insertion_sort_from(v, d);
+		return;
+	}
+
+	let bucket = |s: &str, d: usize| byte_at(s, d).map_or(0, |b| b as usize + 1);
+
+	// Skip the bytes everyone shares (long common prefixes) in place rather
+	// than a stack frame per byte.
+	let counts = loop {
+		let mut counts = [0usize; 257];
+		for &s in v.iter() {
+			counts[bucket(s, d)] += 1;
+		}
+		if !counts[1..].contains(&n) {
+			break counts;
+		}
+		d += 1;
+	};
+
+	let mut starts = [0usize; 257];
+	let mut sum = 0;
+	for (st, &cnt) in starts.iter_mut().zip(counts.iter()) {
+		*st = sum;
+		sum += cnt;
+	}
+
+	let mut next = starts;
+	for &s in v.iter() {
+		let b = bucket(s, d);
+		scratch[next[b]] = s;
+		next[b] += 1;
+	}
+	v.copy_from_slice(scratch);
+
+	for b in 1..257 {
+		let (lo, hi) = (starts[b], starts[b] + counts[b]);
+		if hi - lo > 1 {
+			msd_radix(&mut v[lo..hi], &mut scratch[lo..hi], d + 1);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn check(keys: &[String], what: &str) {
+		let mut expected = keys.to_vec();
+		expected.sort_unstable();
+		for name in STR_ALGOS {
+			let sorter = StrSort::find(name).unwrap();
+			let got: Vec<String> = if sorter.is_owned() {
+				let mut v = keys.to_vec();
+				sorter.sort_owned(&mut v);
+				v
+			} else {
+				let mut v: Vec<&str> = keys.iter().map(String::as_str).collect();
+				sorter.sort_borrowed(&mut v);
+				v.into_iter().map(str::to_string).collect()
+			};
+			assert!(got == expected, "{} {} n={}", name, what, keys.len());
+		}
+	}
+
+	#[test]
+	fn string_sorts_match_sort_unstable() {
+		let mut x: u64 = 7;
+		let mut next = move || {
+			x ^= x << 13;
+			x ^= x >> 7;
+			x ^= x << 17;
+			x
+		};
+		for n in [0, 1, 2, 15, 16, 17, 100, 1000, 5000] {
+			// Short strings over a small alphabet: shared prefixes, duplicates
+			// and prefixes of one another.
+			let keys: Vec<String> = (0..n).map(|_| (0..next() % 6).map(|_| (b'a' + (next() % 3) as u8) as char).collect()).collect();
+			check(&keys, "short");
+		}
+	}
+
+	// msd_radix walks a common prefix without recursing per byte.
+	#[test]
+	fn string_sorts_long_common_prefix() {
+		let prefix = "p".repeat(100_000);
+		let keys: Vec<String> = (0..100).map(|i| format!("{}{:03}", prefix, (i * 37) % 100)).collect();
+		check(&keys, "long_prefix");
+	}
+}
@@ -0,0 +1,376 @@
+use super::ops::{self, CountOps};
+use super::{RunStats, Sorter};
+use crate::elem::IntElem;
+
+const MIN_MERGE: usize = 64;
+const MIN_GALLOP: usize = 7;
+
+#[derive(Clone, Copy)]
+pub(super) struct Run {
+	pub start: usize,
+	pub len: usize,
+}
+
+// Length of the natural run at the start of v. Strictly descending runs are
+// reversed in place (strictly, so equal keys never swap order).
+pub(super) fn count_run_and_make_ascending<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], is_less: &mut F) -> usize {
+	let n = v.len();
+	if n < 2 {
+		return n;
+	}
+	let mut end = 2;
+	if is_less(&v[1], &v[0]) {
+		while end < n && is_less(&v[end], &v[end - 1]) {
+			end += 1;
+		}
+		ops::reverse(&mut v[..end]);
+	} else {
+		while end < n && !is_less(&v[end], &v[end - 1]) {
+			end += 1;
+		}
+	}
+	end
+}
+
+// v[..sorted] is already sorted; inserts the rest, rightmost position on ties.
+pub(super) fn binary_insertion_sort<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], sorted: usize, is_less: &mut F) {
+	for i in sorted.max(1)..v.len() {
+		let x = v[i].mv();
+		let pos = v[..i].partition_point(|y| !is_less(&x, y));
+		ops::copy_within(v, pos..i, pos + 1);
+		v[pos] = x.mv();
+	}
+}
+
+// Exponential search for the partition point of `p` (true on a prefix of a),
+// starting from the front or the back of the slice.
+fn gallop<T, P: FnMut(&T) -> bool>(a: &[T], from_back: bool, mut p: P) -> usize {
+	let n = a.len();
+	if !from_back {
+		let mut lo = 0;
+		let mut ofs = 1;
+		while lo + ofs <= n && p(&a[lo + ofs - 1]) {
+			lo += ofs;
+			ofs *= 2;
+		}
+		let hi = (lo + ofs - 1).min(n);
+		lo + a[lo..hi].partition_point(p)
+	} else {
+		let mut hi = n;
+		let mut ofs = 1;
+		while ofs <= hi && !p(&a[hi - ofs]) {
+			hi -= ofs;
+			ofs *= 2;
+		}
+		let lo = if ofs > hi { 0 } else { hi - ofs + 1 };
+		lo + a[lo..hi].partition_point(p)
+	}
+}
+
+// Number of elements in a that are <= key.
+fn gallop_right<T, F: FnMut(&T, &T) -> bool>(key: &T, a: &[T], from_back: bool, is_less: &mut F) -> usize {
+	gallop(a, from_back, |x| !is_less(key, x))
+}
+
+// Number of elements in a that are < key.
+fn gallop_left<T, F: FnMut(&T, &T) -> bool>(key: &T, a: &[T], from_back: bool, is_less: &mut F) -> usize {
+	gallop(a, from_back, |x| is_less(x, key))
+}
+
+// Merges v[..la] and v[la..] with v[..la] copied out to buf; used when la <= lb.
+fn merge_lo<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], la: usize, buf: &mut Vec<T>, min_gallop: &mut usize, is_less: &mut F) {
+	buf.clear();
+	T::on_moves(la);
+	buf.extend_from_slice(&v[..la]);
+	let a = &buf[..];
+	let n = v.len();
+	let (mut i, mut j, mut d) = (0, la, 0);
+	let mut mg = *min_gallop;
+
+	'outer: loop {
+		let (mut ca, mut cb) = (0, 0);
+		loop {
+			if is_less(&v[j], &a[i]) {
+				v[d] = v[j].mv();
+				d += 1;
+				j += 1;
+				cb += 1;
+				ca = 0;
+				if j == n {
+					break 'outer;
+				}
+			} else {
+				v[d] = a[i].mv();
+				d += 1;
+				i += 1;
+				ca += 1;
+				cb = 0;
+				if i == la {
+					break 'outer;
+				}
+			}
+			if ca >= mg || cb >= mg {
+				break;
+			}
+		}
+
+		loop {
+			let key = v[j];
+			let k = gallop_right(&key, &a[i..], false, is_less);
+			ops::copy(&mut v[d..d + k], &a[i..i + k]);
+			d += k;
+			i += k;
+			if i == la {
+				break 'outer;
+			}
+			v[d] = v[j].mv();
+			d += 1;
+			j += 1;
+			if j == n {
+				break 'outer;
+			}
+
+			let key = a[i];
+			let k2 = gallop_left(&key, &v[j..], false, is_less);
+			ops::copy_within(v, j..j + k2, d);
+			d += k2;
+			j += k2;
+			if j == n {
+				break 'outer;
+			}
+			v[d] = a[i].mv();
+			d += 1;
+			i += 1;
+			if i == la {
+				break 'outer;
+			}
+
+			mg = mg.saturating_sub(1).max(1);
+			if k < MIN_GALLOP && k2 < MIN_GALLOP {
+				break;
+			}
+		}
+		mg += 2;
+	}
+
+	// Whatever is left of b is already in place.
+	ops::copy(&mut v[d..d + (la - i)], &a[i..]);
+	*min_gallop = mg;
+}
+
+// Mirror image of merge_lo: v[la..] is copied out and the merge runs backwards.
+fn merge_hi<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], la: usize, buf: &mut Vec<T>, min_gallop: &mut usize, is_less: &mut F) {
+	buf.clear();
+	T::on_moves(v.len() - la);
+	buf.extend_from_slice(&v[la..]);
+	let b = &buf[..];
+	let (mut i, mut j, mut d) = (la, b.len(), v.len());
+	let mut mg = *min_gallop;
+
+	'outer: loop {
+		let (mut ca, mut cb) = (0, 0);
+		loop {
+			if is_less(&b[j - 1], &v[i - 1]) {
+				d -= 1;
+				v[d] = v[i - 1].mv();
+				i -= 1;
+				ca += 1;
+				cb = 0;
+				if i == 0 {
+					break 'outer;
+				}
+			} else {
+				d -= 1;
+				v[d] = b[j - 1].mv();
+				j -= 1;
+				cb += 1;
+				ca = 0;
+				if j == 0 {
+					break 'outer;
+				}
+			}
+			if ca >= mg || cb >= mg {
+				break;
+			}
+		}
+
+		loop {
+			let key = b[j - 1];
+			let pp = gallop_right(&key, &v[..i], true, is_less);
+			let k = i - pp;
+			d -= k;
+			ops::copy_within(v, pp..i, d);
+			i = pp;
+			if i == 0 {
+				break 'outer;
+			}
+			d -= 1;
+			v[d] = b[j - 1].mv();
+			j -= 1;
+			if j == 0 {
+				break 'outer;
+			}
+
+			let key = v[i - 1];
+			let pp = gallop_left(&key, &b[..j], true, is_less);
+			let k2 = j - pp;
+			d -= k2;
+			ops::copy(&mut v[d..d + k2], &b[pp..j]);
+			j = pp;
+			if j == 0 {
+				break 'outer;
+			}
+			d -= 1;
+			v[d] = v[i - 1].mv();
+			i -= 1;
+			if i == 0 {
+				break 'outer;
+			}
+
+			mg = mg.saturating_sub(1).max(1);
+			if k < MIN_GALLOP && k2 < MIN_GALLOP {
+				break;
+			}
+		}
+		mg += 2;
+	}
+
+	// Whatever is left of a is already in place.
+	ops::copy(&mut v[..j], &b[..j]);
+	*min_gallop = mg;
+}
+
+// Stable merge of the adjacent sorted runs v[..la] and v[la..], copying out
+// the shorter one. Pass usize::MAX as min_gallop to disable galloping.
+pub(super) fn merge_runs<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], la: usize, buf: &mut Vec<T>, min_gallop: &mut usize, is_less: &mut F) {
+	let lb = v.len() - la;
+	if la == 0 || lb == 0 || !is_less(&v[la], &v[la - 1]) {
+		return;
+	}
+	if la <= lb {
+		merge_lo(v, la, buf, min_gallop, is_less);
+	} else {
+		merge_hi(v, la, buf, min_gallop, is_less);
+	}
+}
+
+struct MergeState<T> {
+	runs: Vec<Run>,
+	buf: Vec<T>,
+	min_gallop: usize,
+}
+
+impl<T: CountOps> MergeState<T> {
+	fn merge_at<F: FnMut(&T, &T) -> bool>(&mut self, v: &mut [T], at: usize, is_less: &mut F) {
+		let a = self.runs[at];
+		let b = self.runs[at + 1];
+		self.runs[at].len += b.len;
+		self.runs.remove(at + 1);
+
+		// Elements of a that are <= b[0] and of b that are >= a[last] are already in place.
+		let key = v[b.start];
+		let k = gallop_right(&key, &v[a.start..b.start], false, is_less);
+		let start = a.start + k;
+		let la = a.len - k;
+		if la == 0 {
+			return;
+		}
+		let key = v[b.start - 1];
+		let lb = gallop_left(&key, &v[b.start..b.start + b.len], true, is_less);
+		if lb == 0 {
+			return;
+		}
+		merge_runs(&mut v[start..b.start + lb], la, &mut self.buf, &mut self.min_gallop, is_less);
+	}
+
+	// Keeps run lengths on the stack growing faster than Fibonacci, checking
+	// the top four entries (the fixed invariant from de Gouw et al. 2015).
+	fn merge_collapse<F: FnMut(&T, &T) -> bool>(&mut self, v: &mut [T], is_less: &mut F) {
+		while self.runs.len() > 1 {
+			let r = &self.runs;
+			let mut n = r.len() - 2;
+			if (n > 0 && r[n - 1].len <= r[n].len + r[n + 1].len) || (n > 1 && r[n - 2].len <= r[n - 1].len + r[n].len) {
+				if r[n - 1].len < r[n + 1].len {
+					n -= 1;
+				}
+			} else if r[n].len > r[n + 1].len {
+				break;
+			}
+			self.merge_at(v, n, is_less);
+		}
+	}
+
+	fn merge_force_collapse<F: FnMut(&T, &T) -> bool>(&mut self, v: &mut [T], is_less: &mut F) {
+		while self.runs.len() > 1 {
+			let r = &self.runs;
+			let mut n = r.len() - 2;
+			if n > 0 && r[n - 1].len < r[n + 1].len {
+				n -= 1;
+			}
+			self.merge_at(v, n, is_less);
+		}
+	}
+}
+
+fn min_run_length(mut n: usize) -> usize {
+	let mut r = 0;
+	while n >= MIN_MERGE {
+		r |= n & 1;
+		n >>= 1;
+	}
+	n + r
+}
+
+pub fn timsort_by<T: CountOps, F: FnMut(&T, &T) -> bool>(v: &mut [T], stats: &mut RunStats, is_less: &mut F) {
+	*stats = RunStats::default();
+	let n = v.len();
+	if n < 2 {
+		return;
+	}
+
+	let min_run = min_run_length(n);
+	let mut st = MergeState { runs: Vec::new(), buf: Vec::with_capacity(n / 2), min_gallop: MIN_GALLOP };
+
+	let mut lo = 0;
+	while lo < n {
+		let mut len = count_run_and_make_ascending(&mut v[lo..], is_less);
+		if len < min_run {
+			let force = min_run.min(n - lo);
+			binary_insertion_sort(&mut v[lo..lo + force], len, is_less);
+			len = force;
+		}
+		st.runs.push(Run { start: lo, len });
+		stats.runs += 1;
+		stats.max_stack_depth = stats.max_stack_depth.max(st.runs.len());
+		st.merge_collapse(v, is_less);
+		lo += len;
+	}
+	st.merge_force_collapse(v, is_less);
+}
+
+#[derive(Default)]
+pub struct Timsort {
+	last: RunStats,
+}
+
+impl<T: IntElem> Sorter<T> for Timsort {
+	fn name(&self) -> &'static str {
+		"timsort"
+	}
+
+	fn description(&self) -> &'static str {
+		"Timsort: natural runs, minrun, galloping merges"
+	}
+
+	fn is_comparison(&self) -> bool {
+		true
+	}
+
+	fn sort(&mut self, v: &mut [T]) {
+		timsort_by(v, &mut self.last, &mut |a, b| a < b);
+	}
+
+	fn run_stats(&self) -> Option<RunStats> {
+		Some(self.last)
+	}
+}
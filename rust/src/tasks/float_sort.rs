@@ -0,0 +1,108 @@
+use super::Task;
+use crate::elem::FloatElem;
+use crate::sorters::{FloatSort, NanPolicy, Sorter};
+
+pub struct FloatSortTask<T> {
+	sorter: FloatSort,
+	input: Vec<T>,
+	nan_count: usize,
+	// The input ordered by total_cmp; without its NaNs under FilteredToTail
+	expected: Vec<T>,
+	work: Vec<T>,
+}
+
+impl<T: FloatElem> FloatSortTask<T> {
+	pub fn new(sorter: FloatSort) -> Self {
+		FloatSortTask { sorter, input: Vec::new(), nan_count: 0, expected: Vec::new(), work: Vec::new() }
+	}
+}
+
+impl<T: FloatElem> Task<T> for FloatSortTask<T> {
+	fn setup(&mut self, values: &[T]) -> Result<(), String> {
+		self.input = values.to_vec();
+		self.nan_count = values.iter().filter(|x| x.is_nan()).count();
+		self.expected = match self.sorter.nan_policy() {
+			NanPolicy::TotalOrder => values.to_vec(),
+			NanPolicy::FilteredToTail => values.iter().copied().filter(|x| !x.is_nan()).collect(),
+		};
+		self.expected.sort_by(T::total_cmp);
+		Ok(())
+	}
+
+	fn prepare(&mut self) {
+		self.work = self.input.clone();
+	}
+
+	fn run(&mut self, _phase: usize) {
+		self.sorter.sort(&mut self.work);
+	}
+
+	// Compared bitwise, so a NaN or a -0.0 must land exactly where total_cmp
+	// puts it.
+	fn validate(&self) -> bool {
+		match self.sorter.nan_policy() {
+			NanPolicy::TotalOrder => same_bits(&self.work, &self.expected),
+			NanPolicy::FilteredToTail => {
+				if self.work.len() != self.input.len() {
+					return false;
+				}
+				let (numbers, nans) = self.work.split_at(self.work.len() - self.nan_count);
+				// partial_cmp leaves -0.0 and +0.0 in either order, so check the
+				// order, then that the prefix holds the same numbers.
+				let mut canonical = numbers.to_vec();
+				canonical.sort_by(T::total_cmp);
+				nans.iter().all(|x| x.is_nan())
+					&& !numbers.iter().any(|x| x.is_nan())
+					&& numbers.windows(2).all(|w| w[0] <= w[1])
+					&& same_bits(&canonical, &self.expected)
+			}
+		}
+	}
+}
+
+// radix_key is a bijection on bit patterns, so equal keys mean equal bits.
+fn same_bits<T: FloatElem>(a: &[T], b: &[T]) -> bool {
+	a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.radix_key() == y.radix_key())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sorters::FLOAT_ALGOS;
+
+	fn input<T: FloatElem>(from: fn(f64) -> T) -> Vec<T> {
+		let mut v: Vec<T> = (0..1000).map(|i| from(((i * 7919) % 1000) as f64 - 500.5)).collect();
+		let specials = [f64::NAN, -f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 0.0, -0.0, 0.0, -0.0, f64::NAN];
+		for (i, &x) in specials.iter().enumerate() {
+			v[i * 97] = from(x);
+		}
+		v
+	}
+
+	fn check<T: FloatElem>(from: fn(f64) -> T) {
+		let values = input(from);
+		for name in FLOAT_ALGOS {
+			let mut task = FloatSortTask::<T>::new(FloatSort::find(name).unwrap());
+			task.setup(&values).unwrap();
+			task.prepare();
+			task.run(0);
+			assert!(task.validate(), "{} {}", name, T::NAME);
+
+			// A duplicated element in place of another must not pass.
+			let mut broken = task.work.clone();
+			broken[600] = broken[601];
+			task.work = broken;
+			assert!(!task.validate(), "{} {}: duplicate accepted", name, T::NAME);
+		}
+	}
+
+	#[test]
+	fn float_sorts_validate_f32() {
+		check::<f32>(|x| x as f32);
+	}
+
+	#[test]
+	fn float_sorts_validate_f64() {
+		check::<f64>(|x| x);
+	}
+}
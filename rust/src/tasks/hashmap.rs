@@ -0,0 +1,121 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, BuildHasherDefault, Hasher};
+
+use super::Task;
+
+// The multiply-rotate hash rustc uses internally (FxHash): one multiply per
+// word, no DoS resistance. Ported here to avoid a dependency.
+#[derive(Default, Clone, Copy)]
+pub struct FxHasher {
+	hash: u64,
+}
+
+const FX_SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+impl FxHasher {
+	#[inline]
+	fn add(&mut self, word: u64) {
+		self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(FX_SEED);
+	}
+}
+
+impl Hasher for FxHasher {
+	fn write(&mut self, bytes: &[u8]) {
+		let mut chunks = bytes.chunks_exact(8);
+		for c in &mut chunks {
+			self.add(u64::from_le_bytes(c.try_into().unwrap()));
+		}
+		for &b in chunks.remainder() {
+			self.add(b as u64);
+		}
+	}
+
+	#[inline]
+	fn write_u32(&mut self, i: u32) {
+		self.add(i as u64);
+	}
+
+	#[inline]
+	fn write_i32(&mut self, i: i32) {
+		self.add(i as u32 as u64);
+	}
+
+	#[inline]
+	fn write_u64(&mut self, i: u64) {
+		self.add(i);
+	}
+
+	#[inline]
+	fn write_usize(&mut self, i: usize) {
+		self.add(i as u64);
+	}
+
+	fn finish(&self) -> u64 {
+		self.hash
+	}
+}
+
+pub type FxBuild = BuildHasherDefault<FxHasher>;
+// std's default: SipHash-1-3 with per-map random keys
+pub type SipBuild = RandomState;
+
+// Phase "insert" builds a value -> occurrence count map from the dataset;
+// phase "lookup" then queries every dataset value against it.
+pub struct HashMapTask<S> {
+	input: Vec<i32>,
+	distinct: usize,
+	map: HashMap<i32, u32, S>,
+	hits: usize,
+	checksum: u64,
+}
+
+impl<S: BuildHasher + Default> Default for HashMapTask<S> {
+	fn default() -> Self {
+		HashMapTask { input: Vec::new(), distinct: 0, map: HashMap::default(), hits: 0, checksum: 0 }
+	}
+}
+
+impl<S: BuildHasher + Default> Task for HashMapTask<S> {
+	fn setup(&mut self, values: &[i32]) -> Result<(), String> {
+		self.input = values.to_vec();
+		let mut sorted = values.to_vec();
+		sorted.sort_unstable();
+		sorted.dedup();
+		self.distinct = sorted.len();
+		Ok(())
+	}
+
+	// A fresh, empty map every rep, so the insert phase includes table growth.
+	fn prepare(&mut self) {
+		self.map = HashMap::default();
+		self.hits = 0;
+		self.checksum = 0;
+	}
+
+	fn run(&mut self, phase: usize) {
+		if phase == 0 {
+			for &x in &self.input {
+				*self.map.entry(x).or_insert(0) += 1;
+			}
+		} else {
+			for x in &self.input {
+				if let Some(&c) = self.map.get(x) {
+					self.hits += 1;
+					self.checksum += c as u64;
+				}
+			}
+		}
+	}
+
+	fn validate(&self) -> bool {
+		// Each value is seen `count` times during lookup, so the checksum is
+		// the sum of squared counts.
+		let expected: u64 = self.map.values().map(|&c| c as u64 * c as u64).sum();
+		self.map.len() == self.distinct && self.hits == self.input.len() && self.checksum == expected
+	}
+
+	fn phases(&self) -> &'static [&'static str] {
+		&["insert", "lookup"]
+	}
+}
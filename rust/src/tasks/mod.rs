@@ -0,0 +1,260 @@
+mod float_sort;
+mod hashmap;
+mod records;
+mod scan;
+mod search;
+mod select;
+mod setops;
+mod sort;
+mod string_sort;
+
+use crate::elem::{FloatElem, IntElem};
+use crate::sorters::{self, Counted, FloatSort, OpCounts, SortConfig, StrSort};
+
+// A benchmark selectable through --task, over datasets of element type T
+// (only sort supports anything but i32). The harness calls setup() once per
+// dataset, then prepare() + run() for every warmup and measured rep; only
+// run() is timed, and validate() checks the output of the last rep.
+pub trait Task<T = i32> {
+	fn setup(&mut self, values: &[T]) -> Result<(), String>;
+	fn prepare(&mut self);
+	fn run(&mut self, phase: usize);
+	fn validate(&self) -> bool;
+
+	// Tasks with several separately timed phases name them here; run() is
+	// then called once per phase, in order, and each phase gets its own row
+	// with task "<task>_<phase>".
+	fn phases(&self) -> &'static [&'static str] {
+		&[]
+	}
+
+	// Written to the threads column.
+	fn threads(&self) -> usize {
+		1
+	}
+
+	// Whether --threads changes anything; --threads-sweep requires it.
+	fn is_parallel(&self) -> bool {
+		false
+	}
+
+	// Written to the k column (select/topk only).
+	fn k(&self) -> Option<usize> {
+		None
+	}
+
+	// Written to the comparisons/swaps/moves columns (--count-ops only).
+	fn op_counts(&self) -> Option<OpCounts> {
+		None
+	}
+
+	// Written to the payload_bytes column (records only).
+	fn payload_bytes(&self) -> Option<usize> {
+		None
+	}
+
+	// Two-input tasks receive the --dataset-b values through setup_b(),
+	// which the harness calls before setup().
+	fn needs_dataset_b(&self) -> bool {
+		false
+	}
+
+	fn setup_b(&mut self, _values_b: &[T]) {}
+
+	// Called after each measured rep, outside the timed region.
+	fn after_rep(&mut self, _rep: usize) {}
+}
+
+// --max-quadratic-n when not given; also caps antiqsort against quadratic sorts.
+pub const DEFAULT_MAX_QUADRATIC_N: usize = 20_000;
+
+// Everything a task may need from the command line.
+#[derive(Clone, Debug)]
+pub struct TaskOptions {
+	pub sort_cfg: SortConfig,
+	pub max_quadratic_n: usize,
+	pub log_runs: bool,
+	pub k: Option<usize>,
+	pub quantile: Option<f64>,
+	pub bins: usize,
+	pub payload_bytes: usize,
+	pub count_ops: bool,
+}
+
+// For --list-tasks: each task and the algos it accepts.
+const TASKS: &[(&str, &[&str])] = &[
+	("sort", &["see --list-algos"]),
+	("select", &["builtin"]),
+	("topk", &["builtin", "select_sort", "binary_heap"]),
+	("binary_search", &["builtin"]),
+	("dedup", &["builtin"]),
+	("prefix_sum", &["builtin"]),
+	("histogram", &["builtin"]),
+	("hashmap", &["builtin", "siphash", "fxhash"]),
+	("records", &["builtin", "value_unstable", "value_stable", "index_unstable", "index_stable"]),
+	("merge", &["builtin (needs --dataset-b)"]),
+	("intersection", &["builtin (needs --dataset-b)"]),
+	("union", &["builtin (needs --dataset-b)"]),
+	("difference", &["builtin (needs --dataset-b)"]),
+];
+
+fn unknown_algo(task: &str, algo: &str) -> String {
+	format!("Unknown algo for task {}: {} (see --list-tasks)", task, algo)
+}
+
+fn builtin_only(task: &str, algo: &str) -> Result<(), String> {
+	if algo == "builtin" {
+		Ok(())
+	} else {
+		Err(unknown_algo(task, algo))
+	}
+}
+
+// Element types with a task set: each picks its sort implementation, and
+// everything beyond sort is i32-only. String is not an Elem (it has its own
+// dataset format), but sorts through the same Task interface.
+pub trait TaskElem: Sized + 'static {
+	fn build_sort(algo: &str, opts: &TaskOptions) -> Result<Box<dyn Task<Self>>, String>;
+
+	fn build_non_sort(task: &str, _algo: &str, _opts: &TaskOptions) -> Result<Box<dyn Task<Self>>, String> {
+		Err(format!("--task {} only supports i32 datasets", task))
+	}
+}
+
+fn unknown_sort_algo(algo: &str) -> String {
+	format!("Unknown algo: {} (see --list-algos)", algo)
+}
+
+fn build_int_sort<T: IntElem>(algo: &str, opts: &TaskOptions) -> Result<Box<dyn Task<T>>, String> {
+	if opts.count_ops {
+		let sorter = sorters::find::<Counted<T>>(algo, &opts.sort_cfg).ok_or_else(|| unknown_sort_algo(algo))?;
+		return Ok(Box::new(sort::CountedSortTask::new(sorter, opts)));
+	}
+	let sorter = sorters::find::<T>(algo, &opts.sort_cfg).ok_or_else(|| unknown_sort_algo(algo))?;
+	Ok(Box::new(sort::SortTask::new(sorter, opts)))
+}
+
+fn no_count_ops(opts: &TaskOptions, what: &str) -> Result<(), String> {
+	if opts.count_ops {
+		Err(format!("--count-ops is not supported for {}", what))
+	} else {
+		Ok(())
+	}
+}
+
+fn build_float_sort<T: FloatElem>(algo: &str, opts: &TaskOptions) -> Result<Box<dyn Task<T>>, String> {
+	no_count_ops(opts, "float datasets")?;
+	let sorter = FloatSort::find(algo).ok_or_else(|| format!("Unknown algo for float datasets: {} (see --list-algos)", algo))?;
+	Ok(Box::new(float_sort::FloatSortTask::new(sorter)))
+}
+
+impl TaskElem for i32 {
+	fn build_sort(algo: &str, opts: &TaskOptions) -> Result<Box<dyn Task>, String> {
+		build_int_sort(algo, opts)
+	}
+
+	fn build_non_sort(task: &str, algo: &str, opts: &TaskOptions) -> Result<Box<dyn Task>, String> {
+		build_i32(task, algo, opts)
+	}
+}
+
+impl TaskElem for i64 {
+	fn build_sort(algo: &str, opts: &TaskOptions) -> Result<Box<dyn Task<i64>>, String> {
+		build_int_sort(algo, opts)
+	}
+}
+
+impl TaskElem for u64 {
+	fn build_sort(algo: &str, opts: &TaskOptions) -> Result<Box<dyn Task<u64>>, String> {
+		build_int_sort(algo, opts)
+	}
+}
+
+impl TaskElem for f32 {
+	fn build_sort(algo: &str, opts: &TaskOptions) -> Result<Box<dyn Task<f32>>, String> {
+		build_float_sort(algo, opts)
+	}
+}
+
+impl TaskElem for f64 {
+	fn build_sort(algo: &str, opts: &TaskOptions) -> Result<Box<dyn Task<f64>>, String> {
+		build_float_sort(algo, opts)
+	}
+}
+
+impl TaskElem for String {
+	fn build_sort(algo: &str, opts: &TaskOptions) -> Result<Box<dyn Task<String>>, String> {
+		no_count_ops(opts, "string datasets")?;
+		let sorter = StrSort::find(algo).ok_or_else(|| format!("Unknown algo for string datasets: {} (see --list-algos)", algo))?;
+		Ok(Box::new(string_sort::StrSortTask::new(sorter)))
+	}
+}
+
+pub fn build<T: TaskElem>(task: &str, algo: &str, opts: &TaskOptions) -> Result<Box<dyn Task<T>>, String> {
+	if task == "sort" {
+		T::build_sort(algo, opts)
+	} else {
+		T::build_non_sort(task, algo, opts)
+	}
+}
+
+fn build_i32(task: &str, algo: &str, opts: &TaskOptions) -> Result<Box<dyn Task>, String> {
+	no_count_ops(opts, &format!("--task {}", task))?;
+	match task {
+		"select" => {
+			builtin_only(task, algo)?;
+			Ok(Box::new(select::SelectTask::new(opts)))
+		}
+		"topk" => {
+			let method = select::TopK::parse(algo).ok_or_else(|| unknown_algo(task, algo))?;
+			Ok(Box::new(select::TopKTask::new(method, opts)))
+		}
+		"binary_search" => {
+			builtin_only(task, algo)?;
+			Ok(Box::new(search::BinarySearchTask::default()))
+		}
+		"dedup" => {
+			builtin_only(task, algo)?;
+			Ok(Box::new(scan::DedupTask::default()))
+		}
+		"prefix_sum" => {
+			builtin_only(task, algo)?;
+			Ok(Box::new(scan::PrefixSumTask::default()))
+		}
+		"histogram" => {
+			builtin_only(task, algo)?;
+			Ok(Box::new(scan::HistogramTask::new(opts.bins)))
+		}
+		"hashmap" => match algo {
+			"builtin" | "siphash" => Ok(Box::new(hashmap::HashMapTask::<hashmap::SipBuild>::default())),
+			"fxhash" => Ok(Box::new(hashmap::HashMapTask::<hashmap::FxBuild>::default())),
+			_ => Err(unknown_algo(task, algo)),
+		},
+		"records" => {
+			let method = records::RecordSort::parse(algo).ok_or_else(|| unknown_algo(task, algo))?;
+			match opts.payload_bytes {
+				8 => Ok(Box::new(records::RecordSortTask::<8>::new(method))),
+				32 => Ok(Box::new(records::RecordSortTask::<32>::new(method))),
+				128 => Ok(Box::new(records::RecordSortTask::<128>::new(method))),
+				b => Err(format!("--payload-bytes {} not supported (one of {:?})", b, records::PAYLOAD_SIZES)),
+			}
+		}
+		"merge" | "intersection" | "union" | "difference" => {
+			builtin_only(task, algo)?;
+			let op = match task {
+				"merge" => setops::SetOp::Merge,
+				"intersection" => setops::SetOp::Intersection,
+				"union" => setops::SetOp::Union,
+				_ => setops::SetOp::Difference,
+			};
+			Ok(Box::new(setops::SetOpTask::new(op)))
+		}
+		_ => Err(format!("Unknown task: {} (see --list-tasks)", task)),
+	}
+}
+
+pub fn print_list() {
+	for (task, algos) in TASKS {
+		println!("{:<16} {}", task, algos.join(", "));
+	}
+}
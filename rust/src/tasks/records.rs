@@ -0,0 +1,122 @@
+use super::Task;
+
+// Payload sizes accepted by --payload-bytes; each is its own Record type.
+pub const PAYLOAD_SIZES: &[usize] = &[8, 32, 128];
+
+// A key plus P bytes of payload; the first 8 payload bytes hold the record's
+// index in the input, which validation uses to check stability.
+#[derive(Clone, Copy)]
+pub struct Record<const P: usize> {
+	key: i32,
+	payload: [u8; P],
+}
+
+impl<const P: usize> Record<P> {
+	fn new(key: i32, idx: usize) -> Self {
+		let mut payload = [0u8; P];
+		payload[..8].copy_from_slice(&(idx as u64).to_le_bytes());
+		Record { key, payload }
+	}
+
+	fn orig_idx(&self) -> usize {
+		u64::from_le_bytes(self.payload[..8].try_into().unwrap()) as usize
+	}
+}
+
+#[derive(Clone, Copy)]
+pub enum RecordSort {
+	// sort the records themselves, moving key and payload together
+	ValueUnstable,
+	ValueStable,
+	// sort a u32 permutation by key, then gather the records in that order
+	IndexUnstable,
+	IndexStable,
+}
+
+impl RecordSort {
+	pub fn parse(algo: &str) -> Option<RecordSort> {
+		match algo {
+			"builtin" | "value_unstable" => Some(RecordSort::ValueUnstable),
+			"value_stable" => Some(RecordSort::ValueStable),
+			"index_unstable" => Some(RecordSort::IndexUnstable),
+			"index_stable" => Some(RecordSort::IndexStable),
+			_ => None,
+		}
+	}
+
+	fn is_stable(self) -> bool {
+		matches!(self, RecordSort::ValueStable | RecordSort::IndexStable)
+	}
+}
+
+pub struct RecordSortTask<const P: usize> {
+	method: RecordSort,
+	input: Vec<Record<P>>,
+	work: Vec<Record<P>>,
+	perm: Vec<u32>,
+}
+
+impl<const P: usize> RecordSortTask<P> {
+	pub fn new(method: RecordSort) -> Self {
+		RecordSortTask { method, input: Vec::new(), work: Vec::new(), perm: Vec::new() }
+	}
+}
+
+impl<const P: usize> Task for RecordSortTask<P> {
+	fn setup(&mut self, values: &[i32]) -> Result<(), String> {
+		if values.len() > u32::MAX as usize {
+			return Err("records: n does not fit the u32 permutation".to_string());
+		}
+		self.input = values.iter().enumerate().map(|(i, &key)| Record::new(key, i)).collect();
+		Ok(())
+	}
+
+	fn prepare(&mut self) {
+		match self.method {
+			RecordSort::ValueUnstable | RecordSort::ValueStable => {
+				self.work = self.input.clone();
+			}
+			RecordSort::IndexUnstable | RecordSort::IndexStable => {
+				self.perm = (0..self.input.len() as u32).collect();
+				self.work.clear();
+				self.work.reserve(self.input.len());
+			}
+		}
+	}
+
+	fn run(&mut self, _phase: usize) {
+		let input = &self.input;
+		match self.method {
+			RecordSort::ValueUnstable => self.work.sort_unstable_by_key(|r| r.key),
+			RecordSort::ValueStable => self.work.sort_by_key(|r| r.key),
+			RecordSort::IndexUnstable => self.perm.sort_unstable_by_key(|&i| input[i as usize].key),
+			RecordSort::IndexStable => self.perm.sort_by_key(|&i| input[i as usize].key),
+		}
+		if matches!(self.method, RecordSort::IndexUnstable | RecordSort::IndexStable) {
+			self.work.extend(self.perm.iter().map(|&i| input[i as usize]));
+		}
+	}
+
+	// Sorted by key, every input record present exactly once with its own
+	// key, and (for stable methods) equal keys still in input order.
+	fn validate(&self) -> bool {
+		if self.work.len() != self.input.len() {
+			return false;
+		}
+		let mut seen = vec![false; self.input.len()];
+		for r in &self.work {
+			let idx = r.orig_idx();
+			if idx >= seen.len() || seen[idx] || self.input[idx].key != r.key {
+				return false;
+			}
+			seen[idx] = true;
+		}
+		self.work.windows(2).all(|w| {
+			w[0].key < w[1].key || (w[0].key == w[1].key && (!self.method.is_stable() || w[0].orig_idx() < w[1].orig_idx()))
+		})
+	}
+
+	fn payload_bytes(&self) -> Option<usize> {
+		Some(P)
+	}
+}
@@ -0,0 +1,127 @@
+use super::Task;
+
+// Vec::dedup over the sorted dataset (sorting happens in setup, untimed).
+#[derive(Default)]
+pub struct DedupTask {
+	sorted: Vec<i32>,
+	distinct: usize,
+	work: Vec<i32>,
+}
+
+impl Task for DedupTask {
+	fn setup(&mut self, values: &[i32]) -> Result<(), String> {
+		self.sorted = values.to_vec();
+		self.sorted.sort_unstable();
+		self.distinct = if self.sorted.is_empty() { 0 } else { 1 + self.sorted.windows(2).filter(|w| w[0] != w[1]).count() };
+		Ok(())
+	}
+
+	fn prepare(&mut self) {
+		self.work = self.sorted.clone();
+	}
+
+	fn run(&mut self, _phase: usize) {
+		self.work.dedup();
+	}
+
+	fn validate(&self) -> bool {
+		self.work.len() == self.distinct && self.work.windows(2).all(|w| w[0] < w[1])
+	}
+}
+
+// Inclusive prefix sum into an i64 output (i32 sums overflow at these sizes).
+#[derive(Default)]
+pub struct PrefixSumTask {
+	input: Vec<i32>,
+	expected_total: i64,
+	out: Vec<i64>,
+}
+
+impl Task for PrefixSumTask {
+	fn setup(&mut self, values: &[i32]) -> Result<(), String> {
+		self.input = values.to_vec();
+		self.expected_total = values.iter().map(|&x| x as i64).sum();
+		self.out = vec![0; values.len()];
+		Ok(())
+	}
+
+	fn prepare(&mut self) {
+		self.out.fill(0);
+	}
+
+	fn run(&mut self, _phase: usize) {
+		let mut acc = 0i64;
+		for (o, &x) in self.out.iter_mut().zip(&self.input) {
+			acc += x as i64;
+			*o = acc;
+		}
+	}
+
+	fn validate(&self) -> bool {
+		let mut prev = 0i64;
+		for (&o, &x) in self.out.iter().zip(&self.input) {
+			if o - prev != x as i64 {
+				return false;
+			}
+			prev = o;
+		}
+		prev == self.expected_total
+	}
+}
+
+// Equal-width histogram over [min, max] of the dataset; min/max are found in setup.
+pub struct HistogramTask {
+	bins: usize,
+	input: Vec<i32>,
+	min: i64,
+	span: i64,
+	counts: Vec<u64>,
+}
+
+impl HistogramTask {
+	pub fn new(bins: usize) -> Self {
+		HistogramTask { bins, input: Vec::new(), min: 0, span: 1, counts: Vec::new() }
+	}
+
+}
+
+#[inline]
+fn bin_of(x: i32, min: i64, span: i64, bins: usize) -> usize {
+	((x as i64 - min) * bins as i64 / span) as usize
+}
+
+impl Task for HistogramTask {
+	fn setup(&mut self, values: &[i32]) -> Result<(), String> {
+		if self.bins == 0 {
+			return Err("bins must be > 0".to_string());
+		}
+		self.input = values.to_vec();
+		let min = values.iter().copied().min().unwrap_or(0) as i64;
+		let max = values.iter().copied().max().unwrap_or(0) as i64;
+		self.min = min;
+		self.span = max - min + 1;
+		self.counts = vec![0; self.bins];
+		Ok(())
+	}
+
+	fn prepare(&mut self) {
+		self.counts.fill(0);
+	}
+
+	fn run(&mut self, _phase: usize) {
+		for &x in &self.input {
+			self.counts[bin_of(x, self.min, self.span, self.bins)] += 1;
+		}
+	}
+
+	fn validate(&self) -> bool {
+		if self.counts.iter().sum::<u64>() != self.input.len() as u64 {
+			return false;
+		}
+		let mut expected = vec![0u64; self.bins];
+		for &x in &self.input {
+			expected[bin_of(x, self.min, self.span, self.bins)] += 1;
+		}
+		expected == self.counts
+	}
+}
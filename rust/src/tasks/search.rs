@@ -0,0 +1,35 @@
+use super::Task;
+
+// Every dataset value is looked up in a sorted copy of the dataset, in the
+// dataset's original order, so the batch is n lookups that all hit.
+#[derive(Default)]
+pub struct BinarySearchTask {
+	sorted: Vec<i32>,
+	keys: Vec<i32>,
+	found: Vec<usize>,
+}
+
+impl Task for BinarySearchTask {
+	fn setup(&mut self, values: &[i32]) -> Result<(), String> {
+		self.sorted = values.to_vec();
+		self.sorted.sort_unstable();
+		self.keys = values.to_vec();
+		self.found = Vec::with_capacity(values.len());
+		Ok(())
+	}
+
+	fn prepare(&mut self) {
+		self.found.clear();
+	}
+
+	fn run(&mut self, _phase: usize) {
+		for key in &self.keys {
+			// Misses are recorded as usize::MAX and fail validation.
+			self.found.push(self.sorted.binary_search(key).unwrap_or(usize::MAX));
+		}
+	}
+
+	fn validate(&self) -> bool {
+		self.found.len() == self.keys.len() && self.found.iter().zip(&self.keys).all(|(&i, &key)| self.sorted.get(i) == Some(&key))
+	}
+}
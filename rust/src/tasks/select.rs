@@ -0,0 +1,177 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::{Task, TaskOptions};
+
+// Default k for --task topk when neither --k nor --quantile is given.
+const DEFAULT_TOPK: usize = 100;
+
+// k for --task select: explicit --k, otherwise the --quantile rank (median by default).
+fn select_k(n: usize, k: Option<usize>, quantile: Option<f64>) -> usize {
+	k.unwrap_or_else(|| ((n.saturating_sub(1)) as f64 * quantile.unwrap_or(0.5)).round() as usize)
+}
+
+// k for --task topk: explicit --k, otherwise that fraction of n, otherwise DEFAULT_TOPK.
+fn topk_k(n: usize, k: Option<usize>, quantile: Option<f64>) -> usize {
+	match (k, quantile) {
+		(Some(k), _) => k,
+		(None, Some(q)) => (n as f64 * q).round() as usize,
+		(None, None) => DEFAULT_TOPK.min(n),
+	}
+}
+
+// The select_nth_unstable postcondition: nothing before k is greater than
+// v[k] and nothing after it is less.
+fn is_partitioned_at(v: &[i32], k: usize) -> bool {
+	let pivot = v[k];
+	v[..k].iter().all(|&x| x <= pivot) && v[k + 1..].iter().all(|&x| x >= pivot)
+}
+
+#[derive(Clone, Copy)]
+pub enum TopK {
+	// select_nth_unstable to split off the k largest, then sort that prefix
+	SelectSort,
+	// size-k min-heap over a single pass of the input
+	BinaryHeap,
+}
+
+impl TopK {
+	pub fn parse(algo: &str) -> Option<TopK> {
+		match algo {
+			"builtin" | "select_sort" => Some(TopK::SelectSort),
+			"binary_heap" => Some(TopK::BinaryHeap),
+			_ => None,
+		}
+	}
+
+	// The k largest values of v, largest first. SelectSort reorders v.
+	pub fn run(self, v: &mut [i32], k: usize) -> Vec<i32> {
+		if k == 0 {
+			return Vec::new();
+		}
+		match self {
+			TopK::SelectSort => {
+				v.select_nth_unstable_by(k - 1, |a, b| b.cmp(a));
+				let top = &mut v[..k];
+				top.sort_unstable_by(|a, b| b.cmp(a));
+				top.to_vec()
+			}
+			TopK::BinaryHeap => {
+				let mut heap: BinaryHeap<Reverse<i32>> = BinaryHeap::with_capacity(k + 1);
+				for &x in v.iter() {
+					if heap.len() < k {
+						heap.push(Reverse(x));
+					} else if let Some(mut min) = heap.peek_mut() {
+						if x > min.0 {
+							*min = Reverse(x);
+						}
+					}
+				}
+				// Ascending by Reverse is descending by value.
+				heap.into_sorted_vec().into_iter().map(|r| r.0).collect()
+			}
+		}
+	}
+}
+
+pub struct SelectTask {
+	k_opt: Option<usize>,
+	quantile: Option<f64>,
+	k: usize,
+	input: Vec<i32>,
+	work: Vec<i32>,
+}
+
+impl SelectTask {
+	pub fn new(opts: &TaskOptions) -> Self {
+		SelectTask { k_opt: opts.k, quantile: opts.quantile, k: 0, input: Vec::new(), work: Vec::new() }
+	}
+}
+
+impl Task for SelectTask {
+	fn setup(&mut self, values: &[i32]) -> Result<(), String> {
+		let n = values.len();
+		self.k = select_k(n, self.k_opt, self.quantile);
+		if self.k >= n {
+			return Err(format!("k={} out of range for n={}", self.k, n));
+		}
+		self.input = values.to_vec();
+		Ok(())
+	}
+
+	fn prepare(&mut self) {
+		self.work = self.input.clone();
+	}
+
+	fn run(&mut self, _phase: usize) {
+		self.work.select_nth_unstable(self.k);
+	}
+
+	fn validate(&self) -> bool {
+		is_partitioned_at(&self.work, self.k)
+	}
+
+	fn k(&self) -> Option<usize> {
+		Some(self.k)
+	}
+}
+
+pub struct TopKTask {
+	method: TopK,
+	k_opt: Option<usize>,
+	quantile: Option<f64>,
+	k: usize,
+	input: Vec<i32>,
+	work: Vec<i32>,
+	expected: Vec<i32>,
+	top: Vec<i32>,
+}
+
+impl TopKTask {
+	pub fn new(method: TopK, opts: &TaskOptions) -> Self {
+		TopKTask {
+			method,
+			k_opt: opts.k,
+			quantile: opts.quantile,
+			k: 0,
+			input: Vec::new(),
+			work: Vec::new(),
+			expected: Vec::new(),
+			top: Vec::new(),
+		}
+	}
+}
+
+impl Task for TopKTask {
+	fn setup(&mut self, values: &[i32]) -> Result<(), String> {
+		let n = values.len();
+		self.k = topk_k(n, self.k_opt, self.quantile);
+		if self.k > n {
+			return Err(format!("k={} out of range for n={}", self.k, n));
+		}
+		self.input = values.to_vec();
+
+		// Reference answer, computed once outside the timed region.
+		let mut all = values.to_vec();
+		all.sort_unstable_by(|a, b| b.cmp(a));
+		all.truncate(self.k);
+		self.expected = all;
+		Ok(())
+	}
+
+	fn prepare(&mut self) {
+		self.work = self.input.clone();
+	}
+
+	fn run(&mut self, _phase: usize) {
+		self.top = self.method.run(&mut self.work, self.k);
+	}
+
+	fn validate(&self) -> bool {
+		self.top == self.expected
+	}
+
+	fn k(&self) -> Option<usize> {
+		Some(self.k)
+	}
+}
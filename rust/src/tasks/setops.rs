@@ -0,0 +1,139 @@
+use std::collections::BTreeSet;
+
+use super::Task;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SetOp {
+	// all elements of both inputs, duplicates kept
+	Merge,
+	// the rest have set semantics: strictly increasing, distinct output
+	Intersection,
+	Union,
+	Difference,
+}
+
+// Two sorted inputs (sorted in setup, untimed), one two-pointer kernel per op.
+pub struct SetOpTask {
+	op: SetOp,
+	a: Vec<i32>,
+	b: Vec<i32>,
+	expected: Vec<i32>,
+	out: Vec<i32>,
+}
+
+impl SetOpTask {
+	pub fn new(op: SetOp) -> Self {
+		SetOpTask { op, a: Vec::new(), b: Vec::new(), expected: Vec::new(), out: Vec::new() }
+	}
+}
+
+fn merge(a: &[i32], b: &[i32], out: &mut Vec<i32>) {
+	let (mut i, mut j) = (0, 0);
+	while i < a.len() && j < b.len() {
+		if b[j] < a[i] {
+			out.push(b[j]);
+			j += 1;
+		} else {
+			out.push(a[i]);
+			i += 1;
+		}
+	}
+	out.extend_from_slice(&a[i..]);
+	out.extend_from_slice(&b[j..]);
+}
+
+// Index of the first element after the run of v[i].
+#[inline]
+fn skip_equal(v: &[i32], mut i: usize) -> usize {
+	let x = v[i];
+	while i < v.len() && v[i] == x {
+		i += 1;
+	}
+	i
+}
+
+fn set_op(op: SetOp, a: &[i32], b: &[i32], out: &mut Vec<i32>) {
+	let (mut i, mut j) = (0, 0);
+	while i < a.len() && j < b.len() {
+		if a[i] < b[j] {
+			if op != SetOp::Intersection {
+				out.push(a[i]);
+			}
+			i = skip_equal(a, i);
+		} else if b[j] < a[i] {
+			if op == SetOp::Union {
+				out.push(b[j]);
+			}
+			j = skip_equal(b, j);
+		} else {
+			if op != SetOp::Difference {
+				out.push(a[i]);
+			}
+			i = skip_equal(a, i);
+			j = skip_equal(b, j);
+		}
+	}
+	if op != SetOp::Intersection {
+		while i < a.len() {
+			out.push(a[i]);
+			i = skip_equal(a, i);
+		}
+	}
+	if op == SetOp::Union {
+		while j < b.len() {
+			out.push(b[j]);
+			j = skip_equal(b, j);
+		}
+	}
+}
+
+impl Task for SetOpTask {
+	fn needs_dataset_b(&self) -> bool {
+		true
+	}
+
+	fn setup_b(&mut self, values_b: &[i32]) {
+		self.b = values_b.to_vec();
+		self.b.sort_unstable();
+	}
+
+	fn setup(&mut self, values: &[i32]) -> Result<(), String> {
+		self.a = values.to_vec();
+		self.a.sort_unstable();
+
+		// Reference from BTreeSet, independent of the kernels above. For merge
+		// it only covers the distinct values; the length is checked separately.
+		let sa: BTreeSet<i32> = self.a.iter().copied().collect();
+		let sb: BTreeSet<i32> = self.b.iter().copied().collect();
+		self.expected = match self.op {
+			SetOp::Merge | SetOp::Union => sa.union(&sb).copied().collect(),
+			SetOp::Intersection => sa.intersection(&sb).copied().collect(),
+			SetOp::Difference => sa.difference(&sb).copied().collect(),
+		};
+		self.out = Vec::with_capacity(self.a.len() + self.b.len());
+		Ok(())
+	}
+
+	fn prepare(&mut self) {
+		self.out.clear();
+	}
+
+	fn run(&mut self, _phase: usize) {
+		match self.op {
+			SetOp::Merge => merge(&self.a, &self.b, &mut self.out),
+			op => set_op(op, &self.a, &self.b, &mut self.out),
+		}
+	}
+
+	fn validate(&self) -> bool {
+		if self.op != SetOp::Merge {
+			return self.out == self.expected;
+		}
+		if self.out.len() != self.a.len() + self.b.len() || !crate::is_sorted_non_decreasing(&self.out) {
+			return false;
+		}
+		let mut distinct = self.out.clone();
+		distinct.dedup();
+		distinct == self.expected
+	}
+}
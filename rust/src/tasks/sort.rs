@@ -0,0 +1,132 @@
+use super::{Task, TaskOptions};
+use crate::elem::IntElem;
+use crate::sorters::{self, Counted, OpCounts, Sorter};
+
+pub struct SortTask<T> {
+	sorter: Box<dyn Sorter<T>>,
+	threads: usize,
+	max_quadratic_n: usize,
+	log_runs: bool,
+	input: Vec<T>,
+	// The input sorted by slice::sort_unstable, to validate against
+	expected: Vec<T>,
+	work: Vec<T>,
+}
+
+impl<T: IntElem> SortTask<T> {
+	pub fn new(sorter: Box<dyn Sorter<T>>, opts: &TaskOptions) -> Self {
+		let threads = if sorter.is_parallel() { opts.sort_cfg.threads } else { 1 };
+		SortTask {
+			sorter,
+			threads,
+			max_quadratic_n: opts.max_quadratic_n,
+			log_runs: opts.log_runs,
+			input: Vec::new(),
+			expected: Vec::new(),
+			work: Vec::new(),
+		}
+	}
+}
+
+impl<T: IntElem> Task<T> for SortTask<T> {
+	fn setup(&mut self, values: &[T]) -> Result<(), String> {
+		let n = values.len();
+		if self.sorter.is_quadratic() && n > self.max_quadratic_n {
+			return Err(format!(
+				"{} is quadratic; refusing n={} (limit {}, raise with --max-quadratic-n)",
+				self.sorter.name(),
+				n,
+				self.max_quadratic_n
+			));
+		}
+		if self.log_runs && self.sorter.run_stats().is_none() {
+			eprintln!("--log-runs: {} does not report run statistics", self.sorter.name());
+		}
+		self.input = values.to_vec();
+		self.expected = values.to_vec();
+		self.expected.sort_unstable();
+		Ok(())
+	}
+
+	fn prepare(&mut self) {
+		self.work = self.input.clone();
+	}
+
+	fn run(&mut self, _phase: usize) {
+		self.sorter.sort(&mut self.work);
+	}
+
+	fn validate(&self) -> bool {
+		// Catches dropped or duplicated elements, not just disorder.
+		self.work == self.expected
+	}
+
+	fn threads(&self) -> usize {
+		self.threads
+	}
+
+	fn is_parallel(&self) -> bool {
+		self.sorter.is_parallel()
+	}
+
+	fn after_rep(&mut self, rep: usize) {
+		if self.log_runs {
+			if let Some(st) = self.sorter.run_stats() {
+				eprintln!("[runs] rep={} runs={} max_merge_stack={}", rep, st.runs, st.max_stack_depth);
+			}
+		}
+	}
+}
+
+// --count-ops: the same sort over Counted<T>, so every comparison (and, for
+// our own kernels, every swap and move) is tallied. Counters are reset in
+// prepare() and read in after_rep(), before validate() adds its own comparisons.
+pub struct CountedSortTask<T> {
+	inner: SortTask<Counted<T>>,
+	counts_moves: bool,
+	counts: Option<OpCounts>,
+}
+
+impl<T: IntElem> CountedSortTask<T> {
+	pub fn new(sorter: Box<dyn Sorter<Counted<T>>>, opts: &TaskOptions) -> Self {
+		let counts_moves = sorter.counts_moves();
+		CountedSortTask { inner: SortTask::new(sorter, opts), counts_moves, counts: None }
+	}
+}
+
+impl<T: IntElem> Task<T> for CountedSortTask<T> {
+	fn setup(&mut self, values: &[T]) -> Result<(), String> {
+		let wrapped: Vec<Counted<T>> = values.iter().map(|&x| Counted(x)).collect();
+		self.inner.setup(&wrapped)
+	}
+
+	fn prepare(&mut self) {
+		self.inner.prepare();
+		sorters::reset_counts();
+	}
+
+	fn run(&mut self, phase: usize) {
+		self.inner.run(phase);
+	}
+
+	fn validate(&self) -> bool {
+		self.inner.validate()
+	}
+
+	fn threads(&self) -> usize {
+		self.inner.threads()
+	}
+
+	fn is_parallel(&self) -> bool {
+		self.inner.is_parallel()
+	}
+
+	fn op_counts(&self) -> Option<OpCounts> {
+		self.counts
+	}
+
+	fn after_rep(&mut self, rep: usize) {
+		self.counts = Some(sorters::read_counts(self.counts_moves));
+		self.inner.after_rep(rep);
+	}
+}
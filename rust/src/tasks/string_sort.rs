@@ -0,0 +1,61 @@
+use std::sync::OnceLock;
+
+use super::Task;
+use crate::sorters::StrSort;
+
+// Borrowed algos sort &str into the dataset, so it has to outlive the task.
+// A bench process loads one dataset, so it is leaked once, on first setup(),
+// and every later setup() must pass the same values.
+static ARENA: OnceLock<&'static [String]> = OnceLock::new();
+
+pub struct StrSortTask {
+	sorter: StrSort,
+	arena: &'static [String],
+	expected: Vec<String>,
+	owned: Vec<String>,
+	borrowed: Vec<&'static str>,
+}
+
+impl StrSortTask {
+	pub fn new(sorter: StrSort) -> Self {
+		StrSortTask { sorter, arena: &[], expected: Vec::new(), owned: Vec::new(), borrowed: Vec::new() }
+	}
+}
+
+impl Task<String> for StrSortTask {
+	fn setup(&mut self, values: &[String]) -> Result<(), String> {
+		let arena = *ARENA.get_or_init(|| Box::leak(values.to_vec().into_boxed_slice()));
+		if arena != values {
+			return Err("string sort: only one dataset per process".to_string());
+		}
+		self.arena = arena;
+		self.expected = values.to_vec();
+		self.expected.sort_unstable();
+		Ok(())
+	}
+
+	// Cloning the Strings allocates, so it stays out of the timed run().
+	fn prepare(&mut self) {
+		if self.sorter.is_owned() {
+			self.owned = self.arena.to_vec();
+		} else {
+			self.borrowed = self.arena.iter().map(String::as_str).collect();
+		}
+	}
+
+	fn run(&mut self, _phase: usize) {
+		if self.sorter.is_owned() {
+			self.sorter.sort_owned(&mut self.owned);
+		} else {
+			self.sorter.sort_borrowed(&mut self.borrowed);
+		}
+	}
+
+	fn validate(&self) -> bool {
+		if self.sorter.is_owned() {
+			self.owned == self.expected
+		} else {
+			self.borrowed.len() == self.expected.len() && self.borrowed.iter().zip(&self.expected).all(|(a, b)| *a == b)
+		}
+	}
+}
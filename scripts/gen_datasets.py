@@ -8,15 +8,33 @@ Binary format (little-endian):
 - u32 n
 - n * i32 values
 
+With --elem-type i64/u64/f32/f64 the values have that type instead, and files
+are named <dist>_n<n>_seed<seed>.<type>.bin (only the Rust harness reads these).
+
+With --elem-type str each value is a u32 byte length followed by that many
+bytes of UTF-8, in <dist>_n<n>_seed<seed>.str.bin.
+
 Distributions:
 - random         : uniform random int32
 - sorted         : sorted ascending
 - reversed       : sorted descending
 - dups           : many duplicates (values from a small range)
 - nearly_sorted  : mostly sorted, with a small fraction of random swaps
+- specials       : (f32/f64 only) random values with NaN, -NaN, +-inf, -0.0 and
+                   +0.0 mixed in
+
+String distributions (--elem-type str, the default set there):
+- random_ascii   : random alphanumeric strings of 4..32 characters
+- shared_prefix  : one of a few 64-character prefixes plus a short random suffix
+- urls           : URL-like keys (scheme, host from a small pool, path, query)
 
 Also writes:
-- datasets/meta/datasets.csv  (distribution,n,seed,path)
+- datasets/meta/datasets.csv  (distribution,n,seed,path,generator)
+  (datasets_<type>.csv for other types, so run_all.sh keeps seeing only i32 files)
+
+The Rust port (`bench gen`) writes the same names with different values; the
+generator column records which one wrote each file, and neither generator's
+--force replaces the other's files.
 
 Example:
   python3 scripts/gen_datasets.py --outdir datasets/ints --sizes 1000,10000,100000 --seeds 1,2
@@ -28,6 +46,7 @@ import argparse
 import csv
 import os
 import random
+import string
 import struct
 import sys
 from array import array
@@ -39,12 +58,35 @@ from typing import Iterable, List, Tuple
 INT32_MIN = -(2**31)
 INT32_MAX = 2**31 - 1
 
+# elem type -> (array typecode, min, max); floats draw uniformly from [min, max]
+ELEM_TYPES = {
+	"i32": ("i", INT32_MIN, INT32_MAX),
+	"i64": ("q", -(2**63), 2**63 - 1),
+	"u64": ("Q", 0, 2**64 - 1),
+	"f32": ("f", -1e9, 1e9),
+	"f64": ("d", -1e9, 1e9),
+}
+
+FLOAT_SPECIALS = [float("nan"), -float("nan"), float("inf"), -float("inf"), -0.0, 0.0]
+
+# Written to the manifest's generator column
+GENERATOR = "gen_datasets.py"
+RUST_GENERATOR = "bench-gen"
+
+DEFAULT_DISTS = "random,sorted,reversed,dups,nearly_sorted"
+DEFAULT_STR_DISTS = "random_ascii,shared_prefix,urls"
+
+ALNUM = string.ascii_letters + string.digits
+URL_HOSTS = ["www.example.com", "api.example.com", "cdn.example.net", "shop.example.org", "docs.example.io"]
+URL_WORDS = ["users", "items", "search", "static", "v1", "v2", "images", "orders", "account", "blog", "tags"]
+
 
 @dataclass(frozen=True)
 class DatasetSpec:
 	distribution: str
 	n: int
 	seed: int
+	elem_type: str = "i32"
 
 
 def parse_int_list(value: str) -> List[int]:
@@ -61,52 +103,70 @@ def ensure_dir(p: Path) -> None:
 	p.mkdir(parents=True, exist_ok=True)
 
 
+def write_bin_strings(path: Path, values: List[str]) -> None:
+	"""
+	Write values as:
+	- u32 n (little-endian)
+	- per string: u32 byte length (little-endian), then the UTF-8 bytes
+	"""
+	with path.open("wb") as f:
+		f.write(struct.pack("<I", len(values)))
+		for s in values:
+			b = s.encode("utf-8")
+			f.write(struct.pack("<I", len(b)))
+			f.write(b)
+
+
 def write_bin_int32_le(path: Path, values: array) -> None:
 	"""
 	Write values as:
 	- u32 n (little-endian)
 	- i32[n] values (little-endian)
+
+	Works the same for 8-byte arrays ('q'/'Q'), giving the i64/u64 variant.
 	"""
 	n = len(values)
 	with path.open("wb") as f:
 		f.write(struct.pack("<I", n))
 
-		# array('i') is native-endian; ensure little-endian on disk.
+		# array is native-endian; ensure little-endian on disk.
 		out = values
 		if sys.byteorder != "little":
-			out = array("i", values)  # copy
+			out = array(values.typecode, values)  # copy
 			out.byteswap()
 
 		out.tofile(f)
 
 
-def gen_random(rng: random.Random, n: int) -> array:
-	# Full int32 range; plenty of variety.
-	a = array("i", (rng.randint(INT32_MIN, INT32_MAX) for _ in range(n)))
+def gen_random(rng: random.Random, n: int, elem_type: str = "i32") -> array:
+	# Full range of the element type; plenty of variety.
+	code, lo, hi = ELEM_TYPES[elem_type]
+	draw = rng.uniform if code in "fd" else rng.randint
+	a = array(code, (draw(lo, hi) for _ in range(n)))
 	return a
 
 
-def gen_sorted(rng: random.Random, n: int) -> array:
-	a = gen_random(rng, n)
-	a = array("i", sorted(a))
+def gen_sorted(rng: random.Random, n: int, elem_type: str = "i32") -> array:
+	a = gen_random(rng, n, elem_type)
+	a = array(a.typecode, sorted(a))
 	return a
 
 
-def gen_reversed(rng: random.Random, n: int) -> array:
-	a = gen_sorted(rng, n)
+def gen_reversed(rng: random.Random, n: int, elem_type: str = "i32") -> array:
+	a = gen_sorted(rng, n, elem_type)
 	a.reverse()
 	return a
 
 
-def gen_dups(rng: random.Random, n: int, distinct: int = 128) -> array:
+def gen_dups(rng: random.Random, n: int, distinct: int = 128, elem_type: str = "i32") -> array:
 	# Many duplicates: values from 0..distinct-1
-	a = array("i", (rng.randrange(distinct) for _ in range(n)))
+	a = array(ELEM_TYPES[elem_type][0], (rng.randrange(distinct) for _ in range(n)))
 	return a
 
 
-def gen_nearly_sorted(rng: random.Random, n: int, swap_fraction: float = 0.01) -> array:
+def gen_nearly_sorted(rng: random.Random, n: int, swap_fraction: float = 0.01, elem_type: str = "i32") -> array:
 	# Start sorted, then do k random swaps.
-	a = gen_sorted(rng, n)
+	a = gen_sorted(rng, n, elem_type)
 	k = int(n * swap_fraction)
 	if n <= 1 or k <= 0:
 		return a
@@ -117,26 +177,91 @@ def gen_nearly_sorted(rng: random.Random, n: int, swap_fraction: float = 0.01) -
 	return a
 
 
+def gen_specials(rng: random.Random, n: int, elem_type: str, special_fraction: float = 0.05) -> array:
+	# Random floats with NaN/inf/signed zeros sprinkled in at random positions.
+	if elem_type not in ("f32", "f64"):
+		raise ValueError("specials needs --elem-type f32 or f64")
+	a = gen_random(rng, n, elem_type)
+	for _ in range(int(n * special_fraction)):
+		a[rng.randrange(n)] = rng.choice(FLOAT_SPECIALS)
+	return a
+
+
+def random_word(rng: random.Random, lo: int, hi: int) -> str:
+	return "".join(rng.choice(ALNUM) for _ in range(rng.randint(lo, hi)))
+
+
+def gen_random_ascii(rng: random.Random, n: int) -> List[str]:
+	return [random_word(rng, 4, 32) for _ in range(n)]
+
+
+def gen_shared_prefix(rng: random.Random, n: int, prefixes: int = 4, prefix_len: int = 64) -> List[str]:
+	# Comparisons have to walk the whole prefix before they find a difference.
+	pool = [random_word(rng, prefix_len, prefix_len) for _ in range(prefixes)]
+	return [rng.choice(pool) + random_word(rng, 1, 8) for _ in range(n)]
+
+
+def gen_urls(rng: random.Random, n: int) -> List[str]:
+	out = []
+	for _ in range(n):
+		scheme = rng.choice(["https", "http"])
+		path = "/".join(rng.choice(URL_WORDS) for _ in range(rng.randint(1, 4)))
+		url = f"{scheme}://{rng.choice(URL_HOSTS)}/{path}/{random_word(rng, 4, 12)}"
+		if rng.random() < 0.5:
+			url += f"?id={rng.randrange(1_000_000)}"
+		out.append(url)
+	return out
+
+
+def build_string_dataset(spec: DatasetSpec) -> List[str]:
+	rng = random.Random(spec.seed)
+
+	dist = spec.distribution
+	if dist == "random_ascii":
+		return gen_random_ascii(rng, spec.n)
+	if dist == "shared_prefix":
+		return gen_shared_prefix(rng, spec.n)
+	if dist == "urls":
+		return gen_urls(rng, spec.n)
+
+	raise ValueError(f"Unknown string distribution: {dist}")
+
+
 def build_dataset(spec: DatasetSpec) -> array:
 	rng = random.Random(spec.seed)
 
 	dist = spec.distribution
 	if dist == "random":
-		return gen_random(rng, spec.n)
+		return gen_random(rng, spec.n, spec.elem_type)
 	if dist == "sorted":
-		return gen_sorted(rng, spec.n)
+		return gen_sorted(rng, spec.n, spec.elem_type)
 	if dist == "reversed":
-		return gen_reversed(rng, spec.n)
+		return gen_reversed(rng, spec.n, spec.elem_type)
 	if dist == "dups":
-		return gen_dups(rng, spec.n)
+		return gen_dups(rng, spec.n, elem_type=spec.elem_type)
 	if dist == "nearly_sorted":
-		return gen_nearly_sorted(rng, spec.n)
+		return gen_nearly_sorted(rng, spec.n, elem_type=spec.elem_type)
+	if dist == "specials":
+		return gen_specials(rng, spec.n, spec.elem_type)
 
 	raise ValueError(f"Unknown distribution: {dist}")
 
 
+def read_generators(csv_path: Path) -> dict[str, str]:
+	"""
+	path -> generator from an existing manifest. Manifests without a generator
+	column predate bench gen, so everything in them came from this script.
+	"""
+	if not csv_path.exists():
+		return {}
+	with csv_path.open(newline="") as f:
+		return {row["path"]: row.get("generator") or GENERATOR for row in csv.DictReader(f) if row.get("path")}
+
+
 def dataset_filename(spec: DatasetSpec) -> str:
-	return f"{spec.distribution}_n{spec.n}_seed{spec.seed}.bin"
+	if spec.elem_type == "i32":
+		return f"{spec.distribution}_n{spec.n}_seed{spec.seed}.bin"
+	return f"{spec.distribution}_n{spec.n}_seed{spec.seed}.{spec.elem_type}.bin"
 
 
 def main() -> int:
@@ -157,8 +282,14 @@ def main() -> int:
 	)
 	parser.add_argument(
 		"--dists",
-		default="random,sorted,reversed,dups,nearly_sorted",
-		help="Comma-separated distributions",
+		default=None,
+		help=f"Comma-separated distributions (default {DEFAULT_DISTS}, or {DEFAULT_STR_DISTS} for str)",
+	)
+	parser.add_argument(
+		"--elem-type",
+		choices=sorted(ELEM_TYPES) + ["str"],
+		default="i32",
+		help="Element type; non-i32 files get a .<type>.bin suffix",
 	)
 	parser.add_argument(
 		"--force",
@@ -178,6 +309,8 @@ def main() -> int:
 	ensure_dir(outdir)
 	ensure_dir(metadir)
 
+	if args.dists is None:
+		args.dists = DEFAULT_STR_DISTS if args.elem_type == "str" else DEFAULT_DISTS
 	dists = [d.strip() for d in args.dists.split(",") if d.strip()]
 	if not dists:
 		print("No distributions provided.", file=sys.stderr)
@@ -191,40 +324,62 @@ def main() -> int:
 				if n < 0:
 					print(f"Invalid size: {n}", file=sys.stderr)
 					return 2
-				specs.append(DatasetSpec(dist, n, seed))
-
-	rows: List[Tuple[str, int, int, str]] = []
+				specs.append(DatasetSpec(dist, n, seed, args.elem_type))
+
+	csv_name = "datasets.csv" if args.elem_type == "i32" else f"datasets_{args.elem_type}.csv"
+	csv_path = metadir / csv_name
+	previous = read_generators(csv_path)
+
+	# bench gen draws different values under the same names; replacing its
+	# files would silently change what every lane measures.
+	if args.force and not args.dry_run:
+		for spec in specs:
+			path = outdir / dataset_filename(spec)
+			if path.exists() and previous.get(path.as_posix()) == RUST_GENERATOR:
+				print(
+					f"{path.as_posix()} was written by {RUST_GENERATOR}; this script draws different values, "
+					"so --force will not replace it (use another --outdir, or delete it first)",
+					file=sys.stderr,
+				)
+				return 2
+
+	rows: List[Tuple[str, int, int, str, str]] = []
 
 	for spec in specs:
 		filename = dataset_filename(spec)
 		path = outdir / filename
 
 		rel_path = str(path.as_posix())
-		rows.append((spec.distribution, spec.n, spec.seed, rel_path))
 
 		if args.dry_run:
+			rows.append((spec.distribution, spec.n, spec.seed, rel_path, GENERATOR))
 			print(f"[dry-run] Would generate: {rel_path}")
 			continue
 
 		if path.exists() and not args.force:
+			# Skipped files keep the generator the old manifest recorded.
+			rows.append((spec.distribution, spec.n, spec.seed, rel_path, previous.get(rel_path, "unknown")))
 			print(f"Skip existing (use --force to overwrite): {rel_path}")
 			continue
 
+		rows.append((spec.distribution, spec.n, spec.seed, rel_path, GENERATOR))
+
 		print(f"Generating: {rel_path}")
-		values = build_dataset(spec)
-		write_bin_int32_le(path, values)
+		if spec.elem_type == "str":
+			write_bin_strings(path, build_string_dataset(spec))
+		else:
+			write_bin_int32_le(path, build_dataset(spec))
 
 	# Write datasets.csv
-	csv_path = metadir / "datasets.csv"
 	if args.dry_run:
 		print(f"[dry-run] Would write meta CSV: {csv_path.as_posix()}")
 		return 0
 
 	with csv_path.open("w", newline="") as f:
 		w = csv.writer(f)
-		w.writerow(["distribution", "n", "seed", "path"])
-		for dist, n, seed, path in rows:
-			w.writerow([dist, n, seed, path])
+		w.writerow(["distribution", "n", "seed", "path", "generator"])
+		for dist, n, seed, path, generator in rows:
+			w.writerow([dist, n, seed, path, generator])
 
 	print(f"Wrote meta CSV: {csv_path.as_posix()}")
 	return 0
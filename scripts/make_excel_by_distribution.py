@@ -58,6 +58,9 @@ def read_summary(summary_path: Path) -> list[dict]:
 			raise SystemExit(f"{summary_path} is missing required columns")
 
 		for row in reader:
+			# Only the cross-language comparison: single-threaded i32 sorts
+			if (row.get("task") or "sort") != "sort" or (row.get("elem_type") or "i32") != "i32" or (row.get("threads") or "1") != "1":
+				continue
 			rows.append({
 				"language": row["language"],
 				"algo": row["algo"],
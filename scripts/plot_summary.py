@@ -12,6 +12,12 @@ from typing import Dict, List, Tuple
 import matplotlib.pyplot as plt
 
 
+# The cross-language comparison: single-threaded i32 sorts. Summaries
+# written before these columns existed contain nothing else.
+def is_baseline(row: dict) -> bool:
+	return (row.get("task") or "sort") == "sort" and (row.get("elem_type") or "i32") == "i32" and (row.get("threads") or "1") == "1"
+
+
 def read_summary(path: Path) -> List[dict]:
 	with path.open(newline="") as f:
 		r = csv.DictReader(f)
@@ -20,6 +26,8 @@ def read_summary(path: Path) -> List[dict]:
 			raise SystemExit(f"{path} missing required columns: {sorted(required)}")
 		rows = []
 		for row in r:
+			if not is_baseline(row):
+				continue
 			row["n"] = int(row["n"])
 			row["runs"] = int(row["runs"])
 			row["median_ms"] = float(row["median_ms"])
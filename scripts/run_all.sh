@@ -14,7 +14,7 @@ DATASETS_CSV="datasets/meta/datasets.csv"
 # Sanity checks
 # --------------------
 if [[ ! -f "$DATASETS_CSV" ]]; then
-  echo "Missing $DATASETS_CSV. Run gen_datasets.py first." >&2
+  echo "Missing $DATASETS_CSV. Run gen_datasets.py (or the Rust bench gen) first." >&2
   exit 1
 fi
 
@@ -36,7 +36,7 @@ javac -d java/out $(find java/src/main/java -name "*.java")
 # Benchmark loop
 # --------------------
 
-tail -n +2 "$DATASETS_CSV" | while IFS=, read -r dist n seed path; do
+tail -n +2 "$DATASETS_CSV" | while IFS=, read -r dist n seed path _generator; do
   # Strip CRLF if present
   path="${path//$'\r'/}"
 
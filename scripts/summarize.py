@@ -10,7 +10,12 @@ Reads results/raw.csv and produces results/summary.csv with:
 - std
 
 Groups by:
-(language, algo, distribution, n)
+(language, task, algo, elem_type, threads, distribution, n, k,
+ payload_bytes, dataset_b_file)
+
+Rows from lanes that only write the original 12 columns count as
+elem_type=i32, threads=1; k, payload_bytes and dataset_b_file stay empty
+for tasks that do not use them.
 
 Usage:
   python3 scripts/summarize.py
@@ -29,6 +34,13 @@ from typing import List
 RAW_PATH = Path("results/raw.csv")
 OUT_PATH = Path("results/summary.csv")
 
+# Value assumed when a row lacks an extended column (or leaves it empty)
+DEFAULTS = {"task": "sort", "elem_type": "i32", "threads": "1", "k": "", "payload_bytes": "", "dataset_b_file": ""}
+
+
+def field(row: dict, name: str) -> str:
+	return row.get(name) or DEFAULTS[name]
+
 
 def percentile(sorted_vals: List[float], p: float) -> float:
 	"""
@@ -80,9 +92,15 @@ def main() -> None:
 
 			key = (
 				row["language"],
+				field(row, "task"),
 				row["algo"],
+				field(row, "elem_type"),
+				int(field(row, "threads")),
 				row["distribution"],
 				int(row["n"]),
+				field(row, "k"),
+				field(row, "payload_bytes"),
+				field(row, "dataset_b_file"),
 			)
 			groups[key].append(float(row["time_ms"]))
 
@@ -92,9 +110,15 @@ def main() -> None:
 		writer = csv.writer(f)
 		writer.writerow([
 			"language",
+			"task",
 			"algo",
+			"elem_type",
+			"threads",
 			"distribution",
 			"n",
+			"k",
+			"payload_bytes",
+			"dataset_b_file",
 			"runs",
 			"median_ms",
 			"iqr_ms",
@@ -102,7 +126,7 @@ def main() -> None:
 			"std_ms",
 		])
 
-		for (language, algo, dist, n), times in sorted(groups.items()):
+		for (language, task, algo, elem_type, threads, dist, n, k, payload_bytes, dataset_b), times in sorted(groups.items()):
 			times.sort()
 			m = median(times)
 			q1 = percentile(times, 25)
@@ -113,9 +137,15 @@ def main() -> None:
 
 			writer.writerow([
 				language,
+				task,
 				algo,
+				elem_type,
+				threads,
 				dist,
 				n,
+				k,
+				payload_bytes,
+				dataset_b,
 				len(times),
 				f"{m:.3f}",
 				f"{iqr:.3f}",